@@ -0,0 +1,76 @@
+use crate::error::{Error, Result};
+use crate::number;
+use proc_macro::{token_stream, Delimiter, Group, Span, TokenTree};
+use std::iter::Peekable;
+
+type Tokens = Peekable<token_stream::IntoIter>;
+
+// Evaluates the integer expression in a `{...}` segment such as `{$n + 1}`,
+// made of integer literals, parentheses, and the operators `+ - * / %` with
+// the usual precedence. Arithmetic is on u64 and overflow is an error.
+pub(crate) fn evaluate(group: &Group) -> Result<u64> {
+    let mut tokens = group.stream().into_iter().peekable();
+    let value = parse_sum(&mut tokens, group.span())?;
+    match tokens.next() {
+        Some(unexpected) => Err(Error::new(unexpected.span(), "expected operator")),
+        None => Ok(value),
+    }
+}
+
+fn parse_sum(tokens: &mut Tokens, scope: Span) -> Result<u64> {
+    let mut value = parse_product(tokens, scope)?;
+    while let Some((op, span)) = next_operator(tokens, &['+', '-']) {
+        let rhs = parse_product(tokens, scope)?;
+        value = match op {
+            '+' => value.checked_add(rhs),
+            _ => value.checked_sub(rhs),
+        }
+        .ok_or_else(|| Error::new(span, "arithmetic overflow"))?;
+    }
+    Ok(value)
+}
+
+fn parse_product(tokens: &mut Tokens, scope: Span) -> Result<u64> {
+    let mut value = parse_atom(tokens, scope)?;
+    while let Some((op, span)) = next_operator(tokens, &['*', '/', '%']) {
+        let rhs = parse_atom(tokens, scope)?;
+        if op != '*' && rhs == 0 {
+            return Err(Error::new(span, "division by zero"));
+        }
+        value = match op {
+            '*' => value.checked_mul(rhs),
+            '/' => Some(value / rhs),
+            _ => Some(value % rhs),
+        }
+        .ok_or_else(|| Error::new(span, "arithmetic overflow"))?;
+    }
+    Ok(value)
+}
+
+fn parse_atom(tokens: &mut Tokens, scope: Span) -> Result<u64> {
+    match tokens.next() {
+        Some(TokenTree::Literal(lit)) => match number::parse_int(&lit.to_string()) {
+            Some(value) => Ok(value),
+            None => Err(Error::new(lit.span(), "expected integer")),
+        },
+        Some(TokenTree::Group(ref group))
+            if group.delimiter() == Delimiter::Parenthesis
+                || group.delimiter() == Delimiter::None =>
+        {
+            evaluate(group)
+        }
+        Some(wrong) => Err(Error::new(wrong.span(), "expected integer")),
+        None => Err(Error::new(scope, "expected integer")),
+    }
+}
+
+fn next_operator(tokens: &mut Tokens, ops: &[char]) -> Option<(char, Span)> {
+    let op = match tokens.peek() {
+        Some(TokenTree::Punct(punct)) if ops.contains(&punct.as_char()) => {
+            (punct.as_char(), punct.span())
+        }
+        _ => return None,
+    };
+    tokens.next();
+    Some(op)
+}
@@ -0,0 +1,99 @@
+// Transliteration of Latin letters with diacritics and of common ligatures
+// to ASCII, as in `Größe` to `Grosse` and `café` to `cafe`. Letters keep
+// their case, so `Æ` becomes `AE`. Returns the first char that has no
+// transliteration, such as punctuation or letters from other scripts.
+pub(crate) fn transliterate(input: &str) -> Result<String, char> {
+    let mut acc = String::new();
+    for ch in input.chars() {
+        if ch.is_ascii() {
+            acc.push(ch);
+            continue;
+        }
+        let base = match base(ch) {
+            Some(base) => base,
+            None => return Err(ch),
+        };
+        if ch.is_uppercase() {
+            acc.push_str(&base.to_uppercase());
+        } else if ch.is_lowercase() {
+            acc.push_str(base);
+        } else {
+            // Titlecase digraphs like `ǅ`.
+            acc.push_str(&base[..1].to_uppercase());
+            acc.push_str(&base[1..]);
+        }
+    }
+    Ok(acc)
+}
+
+// The lowercase ASCII form of a letter, in either case.
+fn base(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        // Latin-1 Supplement
+        '\u{C0}'..='\u{C5}' | '\u{E0}'..='\u{E5}' => "a",
+        '\u{C6}' | '\u{E6}' => "ae",
+        '\u{C7}' | '\u{E7}' => "c",
+        '\u{C8}'..='\u{CB}' | '\u{E8}'..='\u{EB}' => "e",
+        '\u{CC}'..='\u{CF}' | '\u{EC}'..='\u{EF}' => "i",
+        '\u{D0}' | '\u{F0}' => "d",
+        '\u{D1}' | '\u{F1}' => "n",
+        '\u{D2}'..='\u{D6}' | '\u{D8}' | '\u{F2}'..='\u{F6}' | '\u{F8}' => "o",
+        '\u{D9}'..='\u{DC}' | '\u{F9}'..='\u{FC}' => "u",
+        '\u{DD}' | '\u{FD}' | '\u{FF}' => "y",
+        '\u{DE}' | '\u{FE}' => "th",
+        '\u{DF}' => "ss",
+        // Latin Extended-A
+        '\u{100}'..='\u{105}' => "a",
+        '\u{106}'..='\u{10D}' => "c",
+        '\u{10E}'..='\u{111}' => "d",
+        '\u{112}'..='\u{11B}' => "e",
+        '\u{11C}'..='\u{123}' => "g",
+        '\u{124}'..='\u{127}' => "h",
+        '\u{128}'..='\u{131}' => "i",
+        '\u{132}' | '\u{133}' => "ij",
+        '\u{134}' | '\u{135}' => "j",
+        '\u{136}'..='\u{138}' => "k",
+        '\u{139}'..='\u{142}' => "l",
+        '\u{143}'..='\u{14B}' => "n",
+        '\u{14C}'..='\u{151}' => "o",
+        '\u{152}' | '\u{153}' => "oe",
+        '\u{154}'..='\u{159}' => "r",
+        '\u{15A}'..='\u{161}' | '\u{17F}' => "s",
+        '\u{162}'..='\u{167}' => "t",
+        '\u{168}'..='\u{173}' => "u",
+        '\u{174}' | '\u{175}' => "w",
+        '\u{176}'..='\u{178}' => "y",
+        '\u{179}'..='\u{17E}' => "z",
+        // Latin Extended-B
+        '\u{180}' => "b",
+        '\u{192}' => "f",
+        '\u{1A0}' | '\u{1A1}' => "o",
+        '\u{1AF}' | '\u{1B0}' => "u",
+        '\u{1C4}'..='\u{1C6}' | '\u{1F1}'..='\u{1F3}' => "dz",
+        '\u{1C7}'..='\u{1C9}' => "lj",
+        '\u{1CA}'..='\u{1CC}' => "nj",
+        '\u{1CD}' | '\u{1CE}' => "a",
+        '\u{1CF}' | '\u{1D0}' => "i",
+        '\u{1D1}' | '\u{1D2}' => "o",
+        '\u{1D3}'..='\u{1DC}' => "u",
+        '\u{1E6}' | '\u{1E7}' => "g",
+        '\u{218}' | '\u{219}' => "s",
+        '\u{21A}' | '\u{21B}' => "t",
+        // Latin Extended Additional, for Vietnamese
+        '\u{1E9E}' => "ss",
+        '\u{1EA0}'..='\u{1EB7}' => "a",
+        '\u{1EB8}'..='\u{1EC7}' => "e",
+        '\u{1EC8}'..='\u{1ECB}' => "i",
+        '\u{1ECC}'..='\u{1EE3}' => "o",
+        '\u{1EE4}'..='\u{1EF1}' => "u",
+        '\u{1EF2}'..='\u{1EF9}' => "y",
+        // Alphabetic Presentation Forms
+        '\u{FB00}' => "ff",
+        '\u{FB01}' => "fi",
+        '\u{FB02}' => "fl",
+        '\u{FB03}' => "ffi",
+        '\u{FB04}' => "ffl",
+        '\u{FB05}' | '\u{FB06}' => "st",
+        _ => return None,
+    })
+}
@@ -1,14 +1,15 @@
+use crate::config::Config;
 use crate::error::Result;
-use crate::segment::{self, Segment};
-use proc_macro::{Delimiter, Group, Spacing, Span, TokenStream, TokenTree};
+use crate::segment::{self, Context, Segment};
+use proc_macro::{token_stream, Delimiter, Group, Spacing, Span, TokenStream, TokenTree};
 use std::iter;
-use std::mem;
 use std::str::FromStr;
 
 pub fn expand_attr(
     attr: TokenStream,
     span: Span,
     contains_paste: &mut bool,
+    config: &Config,
 ) -> Result<TokenStream> {
     let mut tokens = attr.clone().into_iter();
     let mut leading_colons = 0; // $(::)?
@@ -36,11 +37,10 @@ pub fn expand_attr(
             Some(TokenTree::Punct(ref punct))
                 if punct.as_char() == '=' && leading_path % 3 == 1 =>
             {
-                let mut count = 0;
-                if tokens.inspect(|_| count += 1).all(|tt| is_stringlike(&tt)) && count > 1 {
+                if is_pasteable_value(tokens) {
                     *contains_paste = true;
                     let leading = leading_colons + leading_path;
-                    return do_paste_name_value_attr(attr, span, leading);
+                    return do_paste_name_value_attr(attr, span, leading, config);
                 }
                 return Ok(attr);
             }
@@ -70,6 +70,7 @@ pub fn expand_attr(
                     nested_attr,
                     group.span(),
                     &mut group_contains_paste,
+                    config,
                 )?);
                 expanded.extend(iter::once(tt));
                 nested_attr = TokenStream::new();
@@ -83,6 +84,7 @@ pub fn expand_attr(
             nested_attr,
             group.span(),
             &mut group_contains_paste,
+            config,
         )?);
     }
 
@@ -101,33 +103,25 @@ pub fn expand_attr(
     }
 }
 
-fn do_paste_name_value_attr(attr: TokenStream, span: Span, leading: usize) -> Result<TokenStream> {
+fn do_paste_name_value_attr(
+    attr: TokenStream,
+    span: Span,
+    leading: usize,
+    config: &Config,
+) -> Result<TokenStream> {
     let mut expanded = TokenStream::new();
     let mut tokens = attr.into_iter().peekable();
     expanded.extend(tokens.by_ref().take(leading + 1)); // `doc =`
 
     let mut segments = segment::parse(&mut tokens)?;
 
-    for segment in &mut segments {
-        if let Segment::String(string) = segment {
-            if let Some(open_quote) = string.value.find('"') {
-                if open_quote == 0 {
-                    string.value.truncate(string.value.len() - 1);
-                    string.value.remove(0);
-                } else {
-                    let begin = open_quote + 1;
-                    let end = string.value.rfind('"').unwrap();
-                    let raw_string = mem::replace(&mut string.value, String::new());
-                    for ch in raw_string[begin..end].chars() {
-                        string.value.extend(ch.escape_default());
-                    }
-                }
-            }
-        }
-    }
+    unquote_strings(&mut segments);
 
-    let mut lit = segment::paste(&segments)?;
-    lit.insert(0, '"');
+    let pasted = segment::paste(&segments, Context::Doc, config)?;
+    let mut lit = String::from("\"");
+    for ch in pasted.chars() {
+        lit.extend(ch.escape_debug());
+    }
     lit.push('"');
 
     let mut lit = TokenStream::from_str(&lit)
@@ -140,6 +134,52 @@ fn do_paste_name_value_attr(attr: TokenStream, span: Span, leading: usize) -> Re
     Ok(expanded)
 }
 
+// Replaces string literal segments by their value, so that modifiers see
+// chars rather than escape sequences. The pasted string is escaped again when
+// it becomes a literal.
+fn unquote_strings(segments: &mut [Segment]) {
+    for segment in segments {
+        if let Segment::Group(nested) = segment {
+            unquote_strings(nested);
+        } else if let Segment::String(string) = segment {
+            if let Some(value) = segment::unescape_string(&string.value) {
+                string.value = value;
+            }
+        }
+    }
+}
+
+// More than one token, each of which is stringlike or a group that paste
+// understands: an integer expression in braces, a parenthesized sub-sequence
+// or modifier arguments, or a nested `[< ... >]`.
+fn is_pasteable_value(tokens: token_stream::IntoIter) -> bool {
+    let mut count = 0;
+    for token in tokens {
+        count += 1;
+        let is_group = match &token {
+            TokenTree::Group(group) => match group.delimiter() {
+                Delimiter::Parenthesis | Delimiter::Brace => true,
+                Delimiter::Bracket => match group.stream().into_iter().next() {
+                    Some(TokenTree::Punct(punct)) => punct.as_char() == '<',
+                    _ => false,
+                },
+                Delimiter::None => false,
+            },
+            _ => false,
+        };
+        // A leading `-` is a negative number like `#[x = -1]`, not a paste.
+        let is_punct = count > 1
+            && match &token {
+                TokenTree::Punct(punct) => punct.as_char() == '-' || punct.as_char() == '.',
+                _ => false,
+            };
+        if !(is_group || is_punct || is_stringlike(&token)) {
+            return false;
+        }
+    }
+    count > 1
+}
+
 fn is_stringlike(token: &TokenTree) -> bool {
     match token {
         TokenTree::Ident(_) => true,
@@ -151,10 +191,16 @@ fn is_stringlike(token: &TokenTree) -> bool {
             if group.delimiter() != Delimiter::None {
                 return false;
             }
-            let mut inner = group.stream().into_iter();
-            match inner.next() {
-                Some(first) => inner.next().is_none() && is_stringlike(&first),
-                None => false,
+            let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+            match inner.len() {
+                0 => false,
+                1 => is_stringlike(&inner[0]),
+                // The identifiers and `::` of a `$p:path` fragment.
+                _ => inner.iter().all(|token| match token {
+                    TokenTree::Ident(_) => true,
+                    TokenTree::Punct(punct) => punct.as_char() == ':',
+                    _ => false,
+                }),
             }
         }
         TokenTree::Punct(punct) => {
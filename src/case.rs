@@ -0,0 +1,282 @@
+use crate::config::Config;
+#[cfg(feature = "unicode")]
+use crate::unicode::{is_titlecase, push_titlecase};
+use std::mem;
+
+// How digits are grouped with the letters around them when splitting words.
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum Digits {
+    // `Vec3D` is `Vec3` `D`, `Sha256Hash` is `Sha256` `Hash`.
+    Left,
+    // `Vec3D` is `Vec` `3D`, `Sha256Hash` is `Sha` `256Hash`.
+    Right,
+    // `Vec3D` is `Vec` `3` `D`, `Sha256Hash` is `Sha` `256` `Hash`.
+    Separate,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Class {
+    Upper,
+    Lower,
+    Digit,
+    Other,
+}
+
+impl Class {
+    fn of(ch: char) -> Self {
+        if ch.is_uppercase() || is_titlecase(ch) {
+            Class::Upper
+        } else if ch.is_lowercase() {
+            Class::Lower
+        } else if ch.is_numeric() {
+            Class::Digit
+        } else {
+            Class::Other
+        }
+    }
+}
+
+// Splits an identifier into words. Underscores, hyphens and whitespace
+// separate words, a lowercase letter or digit followed by an uppercase letter
+// starts a new word, and a run of uppercase letters is kept together as one
+// word except for its last letter when that one begins a capitalized word:
+// `HTTPServer` is `HTTP` and `Server`, `IOError` is `IO` and `Error`. Where
+// else digits break words is decided by `config.digits`.
+//
+// With the "unicode" feature, UAX#29 word boundaries also find separators
+// such as the punctuation of other scripts. Letters between two separators
+// are split as above, not at every boundary, because UAX#29 puts one between
+// each pair of ideographs. Finally words are replaced according to the
+// `[words]` table of paste.toml while applying one of its templates.
+pub(crate) fn split_words(input: &str, config: &Config) -> Vec<String> {
+    let mut words = Vec::new();
+
+    #[cfg(not(feature = "unicode"))]
+    split_case(input, config, &mut words);
+
+    #[cfg(feature = "unicode")]
+    {
+        use unicode_segmentation::UnicodeSegmentation;
+        let mut start = None;
+        for (offset, segment) in input.split_word_bound_indices() {
+            if segment.chars().any(char::is_alphanumeric) {
+                start = start.or(Some(offset));
+            } else if let Some(begin) = start.take() {
+                split_case(&input[begin..offset], config, &mut words);
+            }
+        }
+        if let Some(begin) = start {
+            split_case(&input[begin..], config, &mut words);
+        }
+    }
+
+    if !config.words.is_empty() {
+        for word in &mut words {
+            if let Some(replacement) = config.words.get(&word.to_lowercase()) {
+                word.clone_from(replacement);
+            }
+        }
+    }
+
+    words
+}
+
+fn split_case(input: &str, config: &Config, words: &mut Vec<String>) {
+    let mut word = String::new();
+    let mut prev = None;
+    let mut rest = input;
+
+    while let Some(ch) = rest.chars().next() {
+        let after = &rest[ch.len_utf8()..];
+        if is_separator(ch) {
+            if !word.is_empty() {
+                words.push(mem::replace(&mut word, String::new()));
+            }
+            prev = None;
+            rest = after;
+            continue;
+        }
+        let class = Class::of(ch);
+        if is_boundary(prev, class, after.chars().next(), config.digits) && !word.is_empty() {
+            words.push(mem::replace(&mut word, String::new()));
+        }
+        if word.is_empty() {
+            if let Some(acronym) = match_acronym(rest, config) {
+                words.push(acronym.to_owned());
+                prev = None;
+                rest = &rest[acronym.len()..];
+                continue;
+            }
+        }
+        word.push(ch);
+        prev = Some(class);
+        rest = after;
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+}
+
+// A configured acronym at the start of a word is split off as a word of its
+// own, provided that what follows does not continue it: `HTTPURLParser` is
+// `HTTP` `URL` `Parser` and `IPv6Addr` is `IPv6` `Addr` given those acronyms,
+// but `IDE` is not split by the acronym `ID`.
+fn match_acronym<'a>(input: &str, config: &'a Config) -> Option<&'a str> {
+    config.acronyms.iter().map(String::as_str).find(|acronym| {
+        if !input.starts_with(acronym) {
+            return false;
+        }
+        let after = &input[acronym.len()..];
+        let mut chars = after.chars();
+        match chars.next() {
+            None => true,
+            Some(ch) if is_separator(ch) => true,
+            Some(ch) => match Class::of(ch) {
+                Class::Upper => {
+                    chars.next().map(Class::of) == Some(Class::Lower)
+                        || match_acronym(after, config).is_some()
+                }
+                Class::Other => true,
+                Class::Lower | Class::Digit => false,
+            },
+        }
+    })
+}
+
+fn is_separator(ch: char) -> bool {
+    ch == '_' || ch == '-' || ch.is_whitespace()
+}
+
+fn is_boundary(prev: Option<Class>, class: Class, next: Option<char>, digits: Digits) -> bool {
+    let prev = match prev {
+        Some(prev) => prev,
+        None => return false,
+    };
+    match (prev, class) {
+        (Class::Digit, Class::Digit) => false,
+        (_, Class::Digit) => digits != Digits::Left,
+        (Class::Digit, _) => {
+            digits == Digits::Separate || digits == Digits::Left && class == Class::Upper
+        }
+        (Class::Lower, Class::Upper) | (Class::Other, Class::Upper) => true,
+        (Class::Upper, Class::Upper) => next.map(Class::of) == Some(Class::Lower),
+        _ => false,
+    }
+}
+
+pub(crate) fn to_snake(input: &str, config: &Config) -> String {
+    convert(input, config, "_", |acc, _i, word| {
+        push_lowercase(acc, word);
+    })
+}
+
+pub(crate) fn to_camel(input: &str, config: &Config) -> String {
+    convert(input, config, "", |acc, _i, word| {
+        push_capitalized(acc, word, config);
+    })
+}
+
+pub(crate) fn to_lower_camel(input: &str, config: &Config) -> String {
+    convert(input, config, "", |acc, i, word| {
+        if i == 0 {
+            push_lowercase(acc, word);
+        } else {
+            push_capitalized(acc, word, config);
+        }
+    })
+}
+
+pub(crate) fn to_kebab(input: &str, config: &Config) -> String {
+    convert(input, config, "-", |acc, _i, word| {
+        push_lowercase(acc, word);
+    })
+}
+
+pub(crate) fn to_train(input: &str, config: &Config) -> String {
+    convert(input, config, "-", |acc, _i, word| {
+        push_capitalized(acc, word, config);
+    })
+}
+
+pub(crate) fn to_title(input: &str, config: &Config) -> String {
+    convert(input, config, " ", |acc, _i, word| {
+        push_capitalized(acc, word, config);
+    })
+}
+
+pub(crate) fn to_sentence(input: &str, config: &Config) -> String {
+    convert(input, config, " ", |acc, i, word| {
+        if i == 0 {
+            push_capitalized(acc, word, config);
+        } else if let Some(acronym) = find_acronym(word, config) {
+            acc.push_str(acronym);
+        } else {
+            push_lowercase(acc, word);
+        }
+    })
+}
+
+fn convert(
+    input: &str,
+    config: &Config,
+    separator: &str,
+    mut push: impl FnMut(&mut String, usize, &str),
+) -> String {
+    // Leading and trailing underscores are kept as they are, so `_private`
+    // and `__reserved__` survive conversion. Only the interior is split.
+    let interior = input.trim_matches('_');
+    let leading = input.len() - input.trim_start_matches('_').len();
+    let trailing = input.len() - input.trim_end_matches('_').len();
+    if interior.is_empty() {
+        return input.to_owned();
+    }
+
+    let mut acc = input[..leading].to_owned();
+    for (i, word) in split_words(interior, config).iter().enumerate() {
+        if i > 0 {
+            acc.push_str(separator);
+        }
+        push(&mut acc, i, word);
+    }
+    acc.push_str(&input[input.len() - trailing..]);
+    acc
+}
+
+#[cfg(not(feature = "unicode"))]
+fn is_titlecase(_ch: char) -> bool {
+    false
+}
+
+#[cfg(not(feature = "unicode"))]
+fn push_titlecase(acc: &mut String, ch: char) {
+    acc.extend(ch.to_uppercase());
+}
+
+fn push_lowercase(acc: &mut String, word: &str) {
+    acc.push_str(&word.to_lowercase());
+}
+
+// Capitalizes the first letter of a word and lowercases the rest, unless the
+// word is one of the configured acronyms in which case it is written the way
+// the acronym was given.
+fn push_capitalized(acc: &mut String, word: &str, config: &Config) {
+    if let Some(acronym) = find_acronym(word, config) {
+        acc.push_str(acronym);
+        return;
+    }
+    let mut chars = word.chars();
+    if let Some(first) = chars.next() {
+        push_titlecase(acc, first);
+        acc.push_str(&chars.as_str().to_lowercase());
+    }
+}
+
+fn find_acronym<'a>(word: &str, config: &'a Config) -> Option<&'a str> {
+    let lowercase = word.to_lowercase();
+    config
+        .acronyms
+        .iter()
+        .map(String::as_str)
+        .find(|acronym| acronym.to_lowercase() == lowercase)
+}
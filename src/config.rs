@@ -0,0 +1,348 @@
+use crate::case::Digits;
+use crate::error::{Error, Result};
+use crate::segment;
+use crate::toml::{self, Value};
+use proc_macro::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Clone)]
+pub(crate) struct Config {
+    pub digits: Digits,
+    // Sorted longest first so that the longest acronym wins when splitting.
+    pub acronyms: Vec<String>,
+    // Replacements for individual words, keyed by the lowercase word. Only
+    // set while applying a paste.toml template.
+    pub words: BTreeMap<String, String>,
+    pub aliases: Aliases,
+    // Whether to make a pasted identifier raw when it is a keyword.
+    pub raw_keywords: bool,
+}
+
+// The contents of paste.toml. Its acronyms and words apply only within its
+// templates, so that the file can't change how built-in modifiers behave in
+// macros from other crates.
+#[derive(Clone, Default)]
+pub(crate) struct Aliases {
+    // Named modifiers defined by templates like `get_{snake}`.
+    pub templates: BTreeMap<String, Vec<TemplatePart>>,
+    pub acronyms: Vec<String>,
+    pub words: BTreeMap<String, String>,
+}
+
+#[derive(Clone)]
+pub(crate) enum TemplatePart {
+    Text(String),
+    // The modifiers named between braces, applied in order to the segment.
+    Chain(Vec<String>),
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            digits: Digits::Left,
+            acronyms: Vec::new(),
+            words: BTreeMap::new(),
+            aliases: Aliases::default(),
+            raw_keywords: false,
+        }
+    }
+}
+
+impl Config {
+    fn set_acronyms(&mut self, mut acronyms: Vec<String>) {
+        acronyms.sort_by_key(|acronym| Reverse(acronym.len()));
+        self.acronyms = acronyms;
+    }
+
+    // The configuration for the modifiers chained in a paste.toml template.
+    pub(crate) fn for_template(&self) -> Config {
+        let mut config = Config {
+            digits: self.digits,
+            words: self.aliases.words.clone(),
+            raw_keywords: self.raw_keywords,
+            ..Config::default()
+        };
+        let mut acronyms = self.acronyms.clone();
+        acronyms.extend(self.aliases.acronyms.iter().cloned());
+        config.set_acronyms(acronyms);
+        config
+    }
+}
+
+// The modification time and length of paste.toml when it was parsed.
+type Stamp = (SystemTime, u64);
+
+thread_local! {
+    // Parsed paste.toml files by path.
+    static CACHE: RefCell<BTreeMap<PathBuf, (Stamp, Aliases)>> = RefCell::new(BTreeMap::new());
+}
+
+// Reads paste.toml from the root of the crate being compiled, if it has one.
+pub(crate) fn load() -> Result<Aliases> {
+    let dir = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => dir,
+        None => return Ok(Aliases::default()),
+    };
+    let path = Path::new(&dir).join("paste.toml");
+    let metadata = match fs::metadata(&path) {
+        Ok(metadata) => metadata,
+        Err(ref err) if err.kind() == ErrorKind::NotFound => return Ok(Aliases::default()),
+        Err(err) => return Err(read_error(&path, &err)),
+    };
+
+    // The file is parsed again only if it changed since the last invocation.
+    let stamp = metadata
+        .modified()
+        .ok()
+        .map(|modified| (modified, metadata.len()));
+    if let Some(stamp) = stamp {
+        let cached = CACHE.with(|cache| match cache.borrow().get(&path) {
+            Some(&(cached_stamp, ref aliases)) if cached_stamp == stamp => Some(aliases.clone()),
+            _ => None,
+        });
+        if let Some(aliases) = cached {
+            return Ok(aliases);
+        }
+    }
+
+    let source = fs::read_to_string(&path).map_err(|err| read_error(&path, &err))?;
+    let aliases = parse_file(&source)?;
+    if let Some(stamp) = stamp {
+        CACHE.with(|cache| cache.borrow_mut().insert(path, (stamp, aliases.clone())));
+    }
+    Ok(aliases)
+}
+
+fn read_error(path: &Path, err: &io::Error) -> Error {
+    Error::new(
+        Span::call_site(),
+        &format!("failed to read {}: {}", path.display(), err),
+    )
+}
+
+fn parse_file(source: &str) -> Result<Aliases> {
+    let mut aliases = Aliases::default();
+
+    let entries = match toml::parse(source) {
+        Ok(entries) => entries,
+        Err(err) => return Err(file_error(err.line, &err.msg)),
+    };
+    for entry in entries {
+        let line = entry.line;
+        match (
+            entry.table.as_ref().map(String::as_str),
+            entry.key.as_str(),
+            entry.value,
+        ) {
+            (None, "acronyms", Value::Array(acronyms)) => {
+                if !acronyms.iter().all(|acronym| is_acronym(acronym)) {
+                    return Err(file_error(line, "expected array of acronyms"));
+                }
+                aliases.acronyms = acronyms;
+            }
+            (Some("words"), word, Value::String(replacement)) => {
+                aliases.words.insert(word.to_lowercase(), replacement);
+            }
+            (Some("modifiers"), name, Value::String(template)) => {
+                if segment::MODIFIERS.contains(&name) {
+                    return Err(file_error(
+                        line,
+                        &format!("`{}` is a built-in modifier", name),
+                    ));
+                }
+                if !name.starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '_')
+                    || name.contains('-')
+                {
+                    return Err(file_error(
+                        line,
+                        &format!("`{}` is not a valid modifier name", name),
+                    ));
+                }
+                let template = parse_template(&template).map_err(|msg| file_error(line, &msg))?;
+                aliases.templates.insert(name.to_owned(), template);
+            }
+            (None, "acronyms", _) | (Some("words"), _, _) | (Some("modifiers"), _, _) => {
+                return Err(file_error(
+                    line,
+                    &format!("invalid value for `{}`", entry.key),
+                ));
+            }
+            (Some(table), _, _) => {
+                return Err(file_error(line, &format!("unknown table `[{}]`", table)));
+            }
+            (None, key, _) => {
+                return Err(file_error(line, &format!("unknown key `{}`", key)));
+            }
+        }
+    }
+
+    Ok(aliases)
+}
+
+fn file_error(line: usize, msg: &str) -> Error {
+    Error::new(Span::call_site(), &format!("paste.toml:{}: {}", line, msg))
+}
+
+// `get_{snake}` is the text `get_` followed by the segment converted with the
+// `snake` modifier. Several modifiers can be chained as in `{snake:upper}`,
+// and `{}` stands for the segment unchanged.
+fn parse_template(template: &str) -> std::result::Result<Vec<TemplatePart>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while !rest.is_empty() {
+        if rest.starts_with('{') {
+            let close = match rest.find('}') {
+                Some(close) => close,
+                None => return Err("unclosed `{` in modifier template".to_owned()),
+            };
+            let mut chain = Vec::new();
+            for name in rest[1..close].split(':').filter(|name| !name.is_empty()) {
+                if !segment::MODIFIERS.contains(&name) {
+                    return Err(format!("unknown modifier `{}` in template", name));
+                }
+                if segment::arity(name).0 > 0 || name == "raw" || name == "join" {
+                    return Err(format!("modifier `{}` cannot be used in a template", name));
+                }
+                chain.push(name.to_owned());
+            }
+            parts.push(TemplatePart::Chain(chain));
+            rest = &rest[close + 1..];
+        } else {
+            let end = rest.find(|ch| ch == '{' || ch == '}').unwrap_or(rest.len());
+            if end == 0 {
+                return Err("unmatched `}` in modifier template".to_owned());
+            }
+            parts.push(TemplatePart::Text(rest[..end].to_owned()));
+            rest = &rest[end..];
+        }
+    }
+    Ok(parts)
+}
+
+const DIGITS_EXPECTED: &str = "expected \"left\", \"right\" or \"separate\"";
+
+fn parse_digits(digits: &str) -> Option<Digits> {
+    match digits {
+        "left" => Some(Digits::Left),
+        "right" => Some(Digits::Right),
+        "separate" => Some(Digits::Separate),
+        _ => None,
+    }
+}
+
+fn is_acronym(acronym: &str) -> bool {
+    !acronym.is_empty() && acronym.chars().all(char::is_alphanumeric)
+}
+
+// Consumes a leading `#![paste(key = "value", ...)]` from the input of a
+// paste! invocation and applies it to `config`. Any other input is returned
+// unchanged.
+pub(crate) fn parse_inner_attr(
+    input: TokenStream,
+    config: &mut Config,
+    contains_paste: &mut bool,
+) -> Result<TokenStream> {
+    let mut tokens = input.clone().into_iter();
+    match (tokens.next(), tokens.next(), tokens.next()) {
+        (
+            Some(TokenTree::Punct(pound)),
+            Some(TokenTree::Punct(bang)),
+            Some(TokenTree::Group(group)),
+        ) if pound.as_char() == '#'
+            && bang.as_char() == '!'
+            && group.delimiter() == Delimiter::Bracket =>
+        {
+            let mut attr = group.stream().into_iter();
+            match (attr.next(), attr.next(), attr.next()) {
+                (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(args)), None)
+                    if ident.to_string() == "paste"
+                        && args.delimiter() == Delimiter::Parenthesis =>
+                {
+                    parse_args(args.stream(), config)?;
+                    *contains_paste = true;
+                    Ok(tokens.collect())
+                }
+                _ => Ok(input),
+            }
+        }
+        _ => Ok(input),
+    }
+}
+
+fn parse_args(args: TokenStream, config: &mut Config) -> Result<()> {
+    let mut tokens = args.into_iter();
+    loop {
+        let key = match tokens.next() {
+            Some(TokenTree::Ident(key)) => key,
+            Some(wrong) => return Err(Error::new(wrong.span(), "expected option name")),
+            None => return Ok(()),
+        };
+        match tokens.next() {
+            Some(TokenTree::Punct(ref punct))
+                if punct.as_char() == '=' && punct.spacing() == Spacing::Alone => {}
+            Some(wrong) => return Err(Error::new(wrong.span(), "expected `=`")),
+            None => return Err(Error::new(key.span(), "expected `=` after option name")),
+        }
+        let value = match tokens.next() {
+            Some(value) => value,
+            None => return Err(Error::new(key.span(), "expected value after `=`")),
+        };
+        match key.to_string().as_str() {
+            "digits" => {
+                config.digits = match parse_digits(&string_value(&value)?) {
+                    Some(digits) => digits,
+                    None => return Err(Error::new(value.span(), DIGITS_EXPECTED)),
+                };
+            }
+            "acronyms" => {
+                let mut acronyms = Vec::new();
+                for acronym in string_value(&value)?.split(',') {
+                    let acronym = acronym.trim();
+                    if !is_acronym(acronym) {
+                        return Err(Error::new(
+                            value.span(),
+                            "expected comma-separated list of acronyms",
+                        ));
+                    }
+                    acronyms.push(acronym.to_owned());
+                }
+                config.set_acronyms(acronyms);
+            }
+            "raw_keywords" => config.raw_keywords = bool_value(&value)?,
+            _ => return Err(Error::new(key.span(), "unsupported paste option")),
+        }
+        match tokens.next() {
+            Some(TokenTree::Punct(ref punct)) if punct.as_char() == ',' => {}
+            Some(wrong) => return Err(Error::new(wrong.span(), "expected `,`")),
+            None => return Ok(()),
+        }
+    }
+}
+
+fn string_value(value: &TokenTree) -> Result<String> {
+    if let TokenTree::Literal(lit) = value {
+        let repr = lit.to_string();
+        if repr.starts_with('"') && repr.ends_with('"') && repr.len() >= 2 {
+            return Ok(repr[1..repr.len() - 1].to_owned());
+        }
+    }
+    Err(Error::new(value.span(), "expected string literal"))
+}
+
+fn bool_value(value: &TokenTree) -> Result<bool> {
+    if let TokenTree::Ident(ident) = value {
+        match ident.to_string().as_str() {
+            "true" => return Ok(true),
+            "false" => return Ok(false),
+            _ => {}
+        }
+    }
+    Err(Error::new(value.span(), "expected `true` or `false`"))
+}
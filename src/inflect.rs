@@ -0,0 +1,248 @@
+// English pluralization and singularization of the last word of a segment,
+// so that `user_category` becomes `user_categories` and `AddressBook` becomes
+// `AddressBooks`. The rules are the usual suffix rules plus a table of
+// irregular and uncountable nouns; they do not try to be a dictionary.
+
+const IRREGULAR: &[(&str, &str)] = &[
+    ("person", "people"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("child", "children"),
+    ("tooth", "teeth"),
+    ("foot", "feet"),
+    ("goose", "geese"),
+    ("mouse", "mice"),
+    ("louse", "lice"),
+    ("ox", "oxen"),
+    ("die", "dice"),
+    ("datum", "data"),
+    ("medium", "media"),
+    ("criterion", "criteria"),
+    ("phenomenon", "phenomena"),
+    ("index", "indices"),
+    ("vertex", "vertices"),
+    ("matrix", "matrices"),
+    ("appendix", "appendices"),
+    ("axis", "axes"),
+    ("cactus", "cacti"),
+    ("focus", "foci"),
+    ("fungus", "fungi"),
+    ("nucleus", "nuclei"),
+    ("radius", "radii"),
+    ("stimulus", "stimuli"),
+    ("alumnus", "alumni"),
+    ("octopus", "octopuses"),
+    ("virus", "viruses"),
+    ("leaf", "leaves"),
+    ("loaf", "loaves"),
+    ("thief", "thieves"),
+    ("knife", "knives"),
+    ("life", "lives"),
+    ("wife", "wives"),
+    ("half", "halves"),
+    ("wolf", "wolves"),
+    ("shelf", "shelves"),
+    ("self", "selves"),
+    ("calf", "calves"),
+    ("hero", "heroes"),
+    ("echo", "echoes"),
+    ("potato", "potatoes"),
+    ("tomato", "tomatoes"),
+    ("veto", "vetoes"),
+    ("quiz", "quizzes"),
+    ("alias", "aliases"),
+    ("atlas", "atlases"),
+    ("bias", "biases"),
+    ("canvas", "canvases"),
+    ("gas", "gases"),
+    ("crisis", "crises"),
+    ("thesis", "theses"),
+    ("hypothesis", "hypotheses"),
+    ("cache", "caches"),
+    ("niche", "niches"),
+    ("movie", "movies"),
+    ("cookie", "cookies"),
+    ("zombie", "zombies"),
+    ("database", "databases"),
+];
+
+// Words ending in `us` whose plural is formed with `es`, so that their
+// plural can be told apart from words like `cause` when singularizing.
+const US_ES: &[&str] = &[
+    "apparatus",
+    "bonus",
+    "bus",
+    "campus",
+    "census",
+    "chorus",
+    "circus",
+    "consensus",
+    "corpus",
+    "genus",
+    "minus",
+    "nexus",
+    "plus",
+    "prospectus",
+    "status",
+    "stylus",
+    "surplus",
+    "syllabus",
+];
+
+const UNCOUNTABLE: &[&str] = &[
+    "aircraft",
+    "bison",
+    "deer",
+    "equipment",
+    "feedback",
+    "fish",
+    "hardware",
+    "information",
+    "jeans",
+    "metadata",
+    "money",
+    "moose",
+    "news",
+    "police",
+    "rice",
+    "series",
+    "sheep",
+    "software",
+    "species",
+    "traffic",
+];
+
+pub(crate) fn to_plural(input: &str) -> String {
+    inflect_last_word(input, pluralize)
+}
+
+pub(crate) fn to_singular(input: &str) -> String {
+    inflect_last_word(input, singularize)
+}
+
+fn inflect_last_word(input: &str, inflect: fn(&str) -> String) -> String {
+    let start = last_word_start(input);
+    let word = &input[start..];
+    if word.is_empty() {
+        return input.to_owned();
+    }
+
+    let lowercase = word.to_lowercase();
+    let inflected = inflect(&lowercase);
+    let mut acc = input[..start].to_owned();
+    let mut chars = word.chars();
+    let first = chars.next().unwrap();
+    if word.chars().count() > 1 && chars.all(|ch| !ch.is_lowercase()) {
+        acc.push_str(&inflected.to_uppercase());
+    } else if first.is_uppercase() {
+        let mut inflected = inflected.chars();
+        if let Some(first) = inflected.next() {
+            acc.extend(first.to_uppercase());
+            acc.push_str(inflected.as_str());
+        }
+    } else {
+        acc.push_str(&inflected);
+    }
+    acc
+}
+
+// The last word is the trailing run of letters, starting after any
+// non-letter or at the capital letter that begins a CamelCase word.
+fn last_word_start(input: &str) -> usize {
+    let mut start = 0;
+    let mut prev = None::<(usize, char)>;
+    let mut prev_upper = false;
+    for (i, ch) in input.char_indices() {
+        if !ch.is_alphabetic() {
+            start = i + ch.len_utf8();
+        } else if let Some((prev_index, prev_ch)) = prev {
+            if ch.is_uppercase() && prev_ch.is_lowercase() {
+                start = i;
+            } else if ch.is_lowercase() && prev_ch.is_uppercase() && prev_upper {
+                start = prev_index;
+            }
+        }
+        prev_upper = prev.map_or(false, |(_, prev_ch)| prev_ch.is_uppercase());
+        prev = Some((i, ch));
+    }
+    if start == input.len() {
+        // No letters at the end, as in `vec3`.
+        return input.len();
+    }
+    start
+}
+
+fn pluralize(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) {
+        return word.to_owned();
+    }
+    for &(singular, plural) in IRREGULAR {
+        if word == singular || word == plural {
+            return plural.to_owned();
+        }
+    }
+
+    if word.ends_with("sis") {
+        replace_suffix(word, 2, "es")
+    } else if word.ends_with('y') && word.len() > 1 && !ends_with_vowel_before(word, 1) {
+        replace_suffix(word, 1, "ies")
+    } else if word.ends_with("us")
+        || word.ends_with("ss")
+        || word.ends_with('x')
+        || word.ends_with('z')
+        || word.ends_with("ch")
+        || word.ends_with("sh")
+    {
+        replace_suffix(word, 0, "es")
+    } else if word.ends_with('s') {
+        word.to_owned()
+    } else {
+        replace_suffix(word, 0, "s")
+    }
+}
+
+fn singularize(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) {
+        return word.to_owned();
+    }
+    for &(singular, plural) in IRREGULAR {
+        if word == plural || word == singular {
+            return singular.to_owned();
+        }
+    }
+
+    if word.ends_with("yses") {
+        // analyses, paralyses
+        replace_suffix(word, 2, "is")
+    } else if word.ends_with("ies") && word.len() > 4 {
+        replace_suffix(word, 3, "y")
+    } else if word.ends_with("uses") && US_ES.contains(&&word[..word.len() - 2])
+        || word.ends_with("sses")
+        || word.ends_with("xes")
+        || word.ends_with("ches")
+        || word.ends_with("shes")
+    {
+        replace_suffix(word, 2, "")
+    } else if word.ends_with('s')
+        && !word.ends_with("ss")
+        && !word.ends_with("us")
+        && !word.ends_with("is")
+    {
+        replace_suffix(word, 1, "")
+    } else {
+        word.to_owned()
+    }
+}
+
+fn ends_with_vowel_before(word: &str, suffix_len: usize) -> bool {
+    match word[..word.len() - suffix_len].chars().next_back() {
+        Some(ch) => "aeiou".contains(ch),
+        None => false,
+    }
+}
+
+fn replace_suffix(word: &str, remove: usize, append: &str) -> String {
+    let mut acc = word[..word.len() - remove].to_owned();
+    acc.push_str(append);
+    acc
+}
@@ -105,13 +105,182 @@
 //!
 //! Use `$var:snake` to convert CamelCase input to snake\_case.
 //! Use `$var:camel` to convert snake\_case to CamelCase.
+//! Use `$var:lower_camel` to convert snake\_case to mixedCase, with the first word
+//! lowercase. `$var:pascal` is another name for `$var:camel`.
 //! These compose, so for example `$var:snake:upper` would give you SCREAMING\_CASE.
 //!
+//! Word boundaries are found at underscores and wherever a lowercase letter or
+//! digit is followed by an uppercase letter. A run of uppercase letters is treated
+//! as a single word, so `HTTPServer` and `IOError` convert to `http_server` and
+//! `io_error` in snake\_case, or `HttpServer` and `IoError` in CamelCase.
+//! Leading and trailing underscores are preserved, so `_private:camel` gives
+//! `_Private`.
+//!
+//! Digits stay attached to the word before them by default, so `Sha256Hash` is
+//! `sha256_hash`. An inner attribute at the start of the `paste!` input selects a
+//! different rule for that invocation: `#![paste(digits = "right")]` attaches
+//! digits to the word after them (`sha_256hash`) and `#![paste(digits =
+//! "separate")]` makes them a word of their own (`sha_256_hash`).
+//!
+//! The same attribute takes a list of acronyms. These are kept together as one
+//! word when splitting, and conversions that capitalize words write them the way
+//! they were listed.
+//!
+//! ```
+//! use paste::paste;
+//!
+//! paste! {
+//!     #![paste(acronyms = "HTTP,URL")]
+//!
+//!     // Defines a struct called `HTTPURLParser`.
+//!     struct [<http_url_parser:camel>];
+//! }
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! Use `$var:plural` or `$var:singular` to inflect the last word of a segment
+//! following the usual English rules, so `$entity:snake:plural` gives
+//! `user_categories` for `UserCategory` and `people` for `Person`.
+//!
+//! Integer segments can be named with `$n:words`, `$n:ordinal` and `$n:alpha`,
+//! so `2` becomes `two`, `second` and `C` respectively. The alphabetic form
+//! counts from `A` for zero, which suits generic parameters in tuple impls;
+//! follow it with `:lower` for field names.
+//!
+//! Some modifiers take string arguments in parentheses. `:strip_prefix("SDL_")`
+//! and `:strip_suffix("Request")` remove a prefix or suffix that the segment
+//! must have, and `:replace("Colour", "Color")` replaces every occurrence of
+//! the first string with the second. So `SDL_CreateWindow:strip_prefix("SDL_"):snake`
+//! gives `create_window`.
+//!
+//! `:re(pattern, replacement)` rewrites the first match of a regular expression,
+//! referring to capture groups in the replacement as `$1` or `${1}`. For example
+//! `$name:re("^(.*)Handler$", "on_$1"):snake` turns `ClickHandler` into
+//! `on_click`. The pattern syntax is a small subset of the `regex` crate's:
+//! classes, groups, alternation and quantifiers, without flags or lookaround. A
+//! segment that does not match is an error, and so is a pattern that backtracks
+//! too much to decide, such as `(a*)*b`.
+//!
+//! To take part of a segment, `$ty:first` and `$ty:last` give its first or last
+//! char, or the first or last few with a count as in `$ty:last(3)`. `:take(n)`
+//! keeps the first `n` chars, `:skip(n)` drops them, and `:slice(begin, end)`
+//! keeps the chars in that range. Counts are in chars rather than bytes, so
+//! `Token:first` is `T` and non-ASCII segments are never split inside a char.
+//!
+//! `$name:len` is the number of chars in the segment, which pastes as a `usize`
+//! literal for sizing arrays as in `[u8; [<$name:len>]]`. Append a suffix such as
+//! `[<$name:len u16>]` if the literal needs a different type.
+//!
+//! Integer segments can be reformatted with `$n:hex`, `$n:oct` and `$n:bin`, which
+//! produce lowercase digits without a prefix, and `$n:pad(width)`, which pads with
+//! leading zeros. So for `31`, `[<REG_0x $n:hex:upper>]` gives `REG_0x1F`,
+//! `[<REG_0x $n:hex:upper:pad(4)>]` gives `REG_0x001F` and `[<op_ $n:pad(3)>]`
+//! gives `op_031`.
+//!
+//! An integer expression in braces is evaluated and pasted as its decimal value,
+//! so with `$n` as `2`, `[<next_state_ {$n + 1}>]` gives `next_state_3`. The
+//! expression may use integer literals, parentheses and the operators `+`, `-`,
+//! `*`, `/` and `%`. Arithmetic is on unsigned 64-bit integers, and overflow or
+//! division by zero is a compile error.
+//!
+//! `$name:hash` appends an underscore and an 8-digit hexadecimal hash of the
+//! segment, and `$name:truncate(n)` shortens a segment longer than `n` chars to
+//! exactly `n` by keeping its beginning and appending the same hash of the whole
+//! segment. The hash is 32-bit FNV-1a of the segment's UTF-8 bytes, so pasted
+//! names are the same on every compiler version and platform.
+//!
+//! Strings from elsewhere, such as `env!` or string literals, can be turned into
+//! something usable in an identifier with `:ident_safe`. It replaces every char
+//! that can't appear in an identifier with an underscore, collapses repeated
+//! underscores, and prefixes an underscore if the result would start with a
+//! char that can't begin an identifier, such as a digit. So
+//! `[<"v1.2 beta+build":ident_safe>]` gives `v1_2_beta_build`.
+//!
+//! For targets that need pure ASCII names, `:ascii` transliterates Latin letters
+//! with diacritics and common ligatures, so `"Größe"` becomes `Grosse` and
+//! `"café"` becomes `cafe`. Any other non-ASCII char is an error.
+//!
+//! A pasted identifier that is a keyword can be emitted as a raw identifier.
+//! `[<$name:raw>]` always produces one, such as `r#type`. Input identifiers
+//! written raw, like `r#type` passed to a macro, stay raw if the pasted result is
+//! still a keyword. With `#![paste(raw_keywords = true)]`, any pasted keyword
+//! becomes raw. This uses the keywords of every edition, including `gen`, which is
+//! harmless in editions where they are not reserved. Lifetimes become raw the same
+//! way, as in `'r#type`.
+//!
+//! A modifier applies to the one segment before it. To apply it to several
+//! segments together, group them in parentheses or in a nested `[<`...`>]`, so
+//! `[<get_ ($a $b):snake>]` and `[<get_ [<$a $b>]:snake>]` snake-case the
+//! concatenation of `$a` and `$b`. A parenthesized group directly after a
+//! modifier is taken as the modifier's arguments, so use the nested form there.
+//!
+//! For optional macro_rules fragments, `_?` is an underscore that is pasted only
+//! between two non-empty segments. `[<$($prefix)? _? $name>]` gives `user_name`
+//! with a prefix of `user` and just `name` without one. `:or(default)` replaces
+//! a group that is empty, so `[<($($prefix)?):or(default) _ $name>]` gives
+//! `default_name` when there is no prefix. It must follow a parenthesized or
+//! nested group, since an empty fragment on its own leaves nothing to apply to.
+//!
+//! Commas split the segments into a list, as from a `$(...),*` repetition, and
+//! `:join(sep)` pastes the non-empty items before it together with `sep` between
+//! them. `[<get_ [<$($part),* :join(_)>]>]` gives `get_user_id` for parts `user`
+//! and `id`. Without `:join`, a comma is an error.
+//!
+//! The punctuation `-` and `.` may appear between segments and paste as an
+//! underscore, for names and versions that arrive from other macros as separate
+//! tokens. `[<$($name)-+ _v $($version).+>]` gives `my_crate_v1_2` for `my-crate`
+//! and `1, 2`. In a doc attribute they are kept as they are.
+//!
+//! A `$p:path` fragment pastes its components joined by underscores, so
+//! `std::io::Error` gives `std_io_Error` and `[<$p:snake>]` gives `std_io_error`.
+//! `:first_segment` and `:last_segment` pick its first or last component, or a
+//! few with a count, so `[<$p:last_segment Ext>]` gives `ErrorExt`, while
+//! `:first` and `:last` still count chars. Any other segment is a path of one
+//! component. In a doc attribute the path is written out with `::`. Paths with
+//! generic arguments are not supported.
+//!
 //! The precise Unicode conversions are as defined by [`str::to_lowercase`] and
-//! [`str::to_uppercase`].
+//! [`str::to_uppercase`]. With the `unicode` Cargo feature enabled, punctuation
+//! found at [Unicode word boundaries] also separates words, so `日本語、テキスト`
+//! is two words, and capitalized words begin with the titlecase form of their
+//! first letter, so `ǆ` becomes `ǅ` rather than `Ǆ`.
 //!
 //! [`str::to_lowercase`]: https://doc.rust-lang.org/std/primitive.str.html#method.to_lowercase
 //! [`str::to_uppercase`]: https://doc.rust-lang.org/std/primitive.str.html#method.to_uppercase
+//! [Unicode word boundaries]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+//!
+//! <br>
+//!
+//! # Project configuration
+//!
+//! A `paste.toml` file next to a crate's Cargo.toml defines named modifiers for
+//! every `paste!` invocation in that crate. Each is a template for the pasted text,
+//! with the segment converted by the modifiers between braces. The file may also
+//! list acronyms and replace individual words, which apply to the case conversions
+//! within these templates only. Built-in modifiers behave the same as in any other
+//! crate, including in macros from other crates.
+//!
+//! ```toml
+//! acronyms = ["HTTP", "URL"]
+//!
+//! [modifiers]
+//! getter = "get_{snake}"         # `[<$field:getter>]` pastes `get_` + `$field:snake`
+//! const_name = "{snake:upper}"
+//!
+//! [words]
+//! colour = "color"
+//! ```
+//!
+//! Cargo does not know that paste reads the file, so a crate with a paste.toml
+//! needs a build script that asks to be rerun when it changes:
+//!
+//! ```
+//! // build.rs
+//! fn main() {
+//!     println!("cargo:rerun-if-changed=paste.toml");
+//! }
+//! ```
 //!
 //! <br>
 //!
@@ -136,6 +305,11 @@
 //!
 //! method_new!(Paste);  // expands to #[doc = "Create a new `Paste` object"]
 //! ```
+//!
+//! In documentation strings the case modifiers `:kebab`, `:train`, `:title` and
+//! `:sentence` are also available, producing `user-id`, `User-Id`, `User Id` and
+//! `User id` respectively from `user_id`. These do not produce identifiers so they
+//! are rejected inside `[<`...`>]`.
 
 #![doc(html_root_url = "https://docs.rs/paste/1.0.15")]
 #![allow(
@@ -149,13 +323,24 @@
 
 extern crate proc_macro;
 
+mod arith;
+mod ascii;
 mod attr;
+mod case;
+mod config;
 mod error;
+mod inflect;
+mod number;
+mod regex;
 mod segment;
+mod toml;
+#[cfg(feature = "unicode")]
+mod unicode;
 
 use crate::attr::expand_attr;
+use crate::config::Config;
 use crate::error::{Error, Result};
-use crate::segment::Segment;
+use crate::segment::{Context, Segment};
 use proc_macro::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
 use std::char;
 use std::iter;
@@ -163,12 +348,25 @@ use std::panic;
 
 #[proc_macro]
 pub fn paste(input: TokenStream) -> TokenStream {
+    let aliases = match config::load() {
+        Ok(aliases) => aliases,
+        Err(err) => return err.to_compile_error(),
+    };
+    let mut config = Config {
+        aliases,
+        ..Config::default()
+    };
     let mut contains_paste = false;
     let flatten_single_interpolation = true;
-    match expand(
-        input.clone(),
-        &mut contains_paste,
-        flatten_single_interpolation,
+    match config::parse_inner_attr(input.clone(), &mut config, &mut contains_paste).and_then(
+        |stripped| {
+            expand(
+                stripped,
+                &mut contains_paste,
+                flatten_single_interpolation,
+                &config,
+            )
+        },
     ) {
         Ok(expanded) => {
             if contains_paste {
@@ -197,6 +395,7 @@ fn expand(
     input: TokenStream,
     contains_paste: &mut bool,
     flatten_single_interpolation: bool,
+    config: &Config,
 ) -> Result<TokenStream> {
     let mut expanded = TokenStream::new();
     let mut lookbehind = Lookbehind::Other;
@@ -224,7 +423,7 @@ fn expand(
                 let span = group.span();
                 if delimiter == Delimiter::Bracket && is_paste_operation(&content) {
                     let segments = parse_bracket_as_segments(content, span)?;
-                    let pasted = segment::paste(&segments)?;
+                    let pasted = segment::paste(&segments, Context::Ident, config)?;
                     let tokens = pasted_to_tokens(pasted, span)?;
                     expanded.extend(tokens);
                     *contains_paste = true;
@@ -242,9 +441,10 @@ fn expand(
                         content,
                         &mut group_contains_paste,
                         flatten_single_interpolation && !is_attribute,
+                        config,
                     )?;
                     if is_attribute {
-                        nested = expand_attr(nested, span, &mut group_contains_paste)?;
+                        nested = expand_attr(nested, span, &mut group_contains_paste, config)?;
                     }
                     let group = if group_contains_paste {
                         let mut group = Group::new(delimiter, nested);
@@ -375,8 +575,16 @@ fn parse_bracket_as_segments(input: TokenStream, scope: Span) -> Result<Vec<Segm
         ));
     }
 
-    for segment in &mut segments {
-        if let Segment::String(string) = segment {
+    normalize_literals(&mut segments)?;
+    Ok(segments)
+}
+
+// Replaces literal segments by the text they contribute to the identifier.
+fn normalize_literals(segments: &mut [Segment]) -> Result<()> {
+    for segment in segments {
+        if let Segment::Group(nested) = segment {
+            normalize_literals(nested)?;
+        } else if let Segment::String(string) = segment {
             if string.value.starts_with("'\\u{") {
                 let hex = &string.value[4..string.value.len() - 2];
                 if let Ok(unsigned) = u32::from_str_radix(hex, 16) {
@@ -387,6 +595,14 @@ fn parse_bracket_as_segments(input: TokenStream, scope: Span) -> Result<Vec<Segm
                     }
                 }
             }
+            if string.value.starts_with('"') || string.value.starts_with('r') {
+                // A string literal; anything it contains that can't be part
+                // of an identifier is left for `:ident_safe` to clean up.
+                if let Some(value) = segment::unescape_string(&string.value) {
+                    string.value = value.replace('-', "_");
+                    continue;
+                }
+            }
             if string.value.contains(&['#', '\\', '.', '+'][..])
                 || string.value.starts_with("b'")
                 || string.value.starts_with("b\"")
@@ -395,10 +611,7 @@ fn parse_bracket_as_segments(input: TokenStream, scope: Span) -> Result<Vec<Segm
                 return Err(Error::new(string.span, "unsupported literal"));
             }
             let mut range = 0..string.value.len();
-            if string.value.starts_with("r\"") {
-                range.start += 2;
-                range.end -= 1;
-            } else if string.value.starts_with(&['"', '\''][..]) {
+            if string.value.starts_with('\'') {
                 range.start += 1;
                 range.end -= 1;
             }
@@ -406,7 +619,7 @@ fn parse_bracket_as_segments(input: TokenStream, scope: Span) -> Result<Vec<Segm
         }
     }
 
-    Ok(segments)
+    Ok(())
 }
 
 fn pasted_to_tokens(mut pasted: String, span: Span) -> Result<TokenStream> {
@@ -439,7 +652,12 @@ fn pasted_to_tokens(mut pasted: String, span: Span) -> Result<TokenStream> {
         pasted.remove(0);
     }
 
-    let ident = match panic::catch_unwind(|| Ident::new(&pasted, span)) {
+    let ident = if pasted.starts_with("r#") {
+        panic::catch_unwind(|| new_raw_ident(&pasted[2..], span))
+    } else {
+        panic::catch_unwind(|| Ident::new(&pasted, span))
+    };
+    let ident = match ident {
         Ok(ident) => TokenTree::Ident(ident),
         Err(_) => {
             return Err(Error::new(
@@ -452,3 +670,26 @@ fn pasted_to_tokens(mut pasted: String, span: Span) -> Result<TokenStream> {
     tokens.extend(iter::once(ident));
     Ok(tokens)
 }
+
+#[cfg(not(no_ident_new_raw))]
+#[allow(clippy::incompatible_msrv)] // guarded by build.rs
+fn new_raw_ident(name: &str, span: Span) -> Ident {
+    Ident::new_raw(name, span)
+}
+
+#[cfg(no_ident_new_raw)]
+fn new_raw_ident(name: &str, span: Span) -> Ident {
+    use std::str::FromStr;
+
+    match TokenStream::from_str(&format!("r#{}", name))
+        .unwrap()
+        .into_iter()
+        .next()
+    {
+        Some(TokenTree::Ident(mut ident)) => {
+            ident.set_span(span);
+            ident
+        }
+        _ => panic!("invalid raw identifier"),
+    }
+}
@@ -0,0 +1,165 @@
+// Integer segments, written the way they appeared as literals: `2`, `0x1F`,
+// `1_000`, `8u8`.
+pub(crate) fn parse_int(repr: &str) -> Option<u64> {
+    let (radix, digits) = if repr.starts_with("0x") {
+        (16, &repr[2..])
+    } else if repr.starts_with("0o") {
+        (8, &repr[2..])
+    } else if repr.starts_with("0b") {
+        (2, &repr[2..])
+    } else {
+        (10, repr)
+    };
+
+    let mut digits = digits;
+    for suffix in &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ] {
+        if digits.ends_with(suffix) {
+            digits = &digits[..digits.len() - suffix.len()];
+            break;
+        }
+    }
+
+    let mut value = 0u64;
+    let mut any_digit = false;
+    for ch in digits.chars() {
+        if ch == '_' {
+            continue;
+        }
+        let digit = ch.to_digit(radix)?;
+        value = value
+            .checked_mul(u64::from(radix))?
+            .checked_add(u64::from(digit))?;
+        any_digit = true;
+    }
+    if any_digit {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+// English words for the number, separated by underscores: 21 is
+// `twenty_one`, 1005 is `one_thousand_five`.
+pub(crate) fn to_words(n: u64) -> String {
+    if n == 0 {
+        return ONES[0].to_owned();
+    }
+
+    let mut groups = Vec::new();
+    let mut rest = n;
+    while rest > 0 {
+        groups.push(rest % 1000);
+        rest /= 1000;
+    }
+
+    let mut words = Vec::new();
+    for (scale, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        push_hundreds(&mut words, group);
+        if scale > 0 {
+            words.push(SCALES[scale]);
+        }
+    }
+    words.join("_")
+}
+
+fn push_hundreds(words: &mut Vec<&'static str>, n: u64) {
+    let hundreds = (n / 100) as usize;
+    let rest = (n % 100) as usize;
+    if hundreds > 0 {
+        words.push(ONES[hundreds]);
+        words.push("hundred");
+    }
+    if rest >= 20 {
+        words.push(TENS[rest / 10]);
+        if rest % 10 > 0 {
+            words.push(ONES[rest % 10]);
+        }
+    } else if rest > 0 {
+        words.push(ONES[rest]);
+    }
+}
+
+// 1 is `first`, 22 is `twenty_second`, 100 is `one_hundredth`.
+pub(crate) fn to_ordinal(n: u64) -> String {
+    let mut words = to_words(n);
+    let last_start = words.rfind('_').map_or(0, |i| i + 1);
+    let last = words.split_off(last_start);
+    let ordinal = match last.as_str() {
+        "one" => "first",
+        "two" => "second",
+        "three" => "third",
+        "five" => "fifth",
+        "eight" => "eighth",
+        "nine" => "ninth",
+        "twelve" => "twelfth",
+        _ => {
+            if last.ends_with('y') {
+                words.push_str(&last[..last.len() - 1]);
+                words.push_str("ieth");
+            } else {
+                words.push_str(&last);
+                words.push_str("th");
+            }
+            return words;
+        }
+    };
+    words.push_str(ordinal);
+    words
+}
+
+// Spreadsheet-style column letters counting from zero: 0 is `A`, 25 is `Z`,
+// 26 is `AA`.
+pub(crate) fn to_alpha(n: u64) -> String {
+    let mut letters = Vec::new();
+    let mut rest = n;
+    loop {
+        letters.push(b'A' + (rest % 26) as u8);
+        if rest < 26 {
+            break;
+        }
+        rest = rest / 26 - 1;
+    }
+    letters.iter().rev().map(|&letter| letter as char).collect()
+}
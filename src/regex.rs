@@ -0,0 +1,472 @@
+// A small backtracking regex engine for the `:re(pattern, replacement)`
+// modifier. It supports the common syntax: literals and escapes, `.`, `^`,
+// `$`, character classes including `\d`, `\w`, `\s` and their negations,
+// capturing and `(?:...)` groups, alternation, and the greedy and lazy
+// quantifiers `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}`. Segments are short, so
+// nothing here is optimized beyond a cap on the number of matching steps.
+
+use std::cell::Cell;
+
+const MAX_STEPS: usize = 100_000;
+
+pub(crate) struct Regex {
+    node: Node,
+    groups: usize,
+}
+
+enum Node {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat(Repeat),
+}
+
+struct Repeat {
+    node: Box<Node>,
+    min: usize,
+    max: Option<usize>,
+    greedy: bool,
+}
+
+struct Class {
+    negated: bool,
+    items: Vec<ClassItem>,
+}
+
+enum ClassItem {
+    Range(char, char),
+    Perl(Perl, bool),
+}
+
+#[derive(Copy, Clone)]
+enum Perl {
+    Digit,
+    Word,
+    Space,
+}
+
+pub(crate) type Captures = Vec<Option<(usize, usize)>>;
+
+impl Regex {
+    pub(crate) fn new(pattern: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            pos: 0,
+            groups: 1,
+        };
+        let node = parser.parse_alternate()?;
+        if parser.pos < parser.chars.len() {
+            return Err("unmatched `)`".to_owned());
+        }
+        Ok(Regex {
+            node,
+            groups: parser.groups,
+        })
+    }
+
+    // Finds the leftmost match in `input`, or returns None if there is no
+    // match. Backtracking can take exponential time on patterns like
+    // `(a*)*b`, so matching gives up after a fixed number of steps.
+    pub(crate) fn find(&self, input: &str) -> Result<Option<Captures>, String> {
+        let chars: Vec<char> = input.chars().collect();
+        let search = Matcher {
+            chars: &chars,
+            steps: Cell::new(0),
+        };
+        for start in 0..=chars.len() {
+            let mut captures = vec![None; self.groups];
+            let matched = search.match_node(&self.node, start, &mut captures, &mut |end, caps| {
+                caps[0] = Some((start, end));
+                true
+            });
+            if search.steps.get() > MAX_STEPS {
+                return Err("pattern too complex".to_owned());
+            }
+            if matched {
+                return Ok(Some(captures));
+            }
+        }
+        Ok(None)
+    }
+
+    // Replaces the match found by `find`. The replacement refers to groups as
+    // `$1` or `${1}`, to the whole match as `$0`, and to a literal dollar sign
+    // as `$$`.
+    pub(crate) fn replace(
+        &self,
+        input: &str,
+        captures: &Captures,
+        replacement: &str,
+    ) -> Result<String, String> {
+        let chars: Vec<char> = input.chars().collect();
+        let (begin, end) = captures[0].unwrap();
+
+        let mut acc: String = chars[..begin].iter().collect();
+        let mut rest = replacement.chars().peekable();
+        while let Some(ch) = rest.next() {
+            if ch != '$' {
+                acc.push(ch);
+                continue;
+            }
+            let braced = rest.peek() == Some(&'{');
+            if braced {
+                rest.next();
+            }
+            let mut digits = String::new();
+            while let Some(&digit) = rest.peek() {
+                if !digit.is_ascii_digit() {
+                    break;
+                }
+                digits.push(digit);
+                rest.next();
+            }
+            if braced && rest.next() != Some('}') {
+                return Err("expected `}` after group number".to_owned());
+            }
+            if digits.is_empty() {
+                if !braced && rest.peek() == Some(&'$') {
+                    rest.next();
+                    acc.push('$');
+                    continue;
+                }
+                return Err("expected group number or `$` after `$`".to_owned());
+            }
+            let group = match digits.parse::<usize>() {
+                Ok(group) if group < self.groups => group,
+                _ => return Err(format!("pattern has no group {}", digits)),
+            };
+            if let Some((begin, end)) = captures[group] {
+                acc.extend(&chars[begin..end]);
+            }
+        }
+        acc.extend(&chars[end..]);
+        Ok(acc)
+    }
+}
+
+struct Matcher<'a> {
+    chars: &'a [char],
+    steps: Cell<usize>,
+}
+
+impl Matcher<'_> {
+    fn match_node(
+        &self,
+        node: &Node,
+        pos: usize,
+        caps: &mut Captures,
+        k: &mut dyn FnMut(usize, &mut Captures) -> bool,
+    ) -> bool {
+        // Once over budget every attempt fails, which unwinds the search.
+        self.steps.set(self.steps.get() + 1);
+        if self.steps.get() > MAX_STEPS {
+            return false;
+        }
+        let chars = self.chars;
+        match node {
+            Node::Char(expected) => chars.get(pos) == Some(expected) && k(pos + 1, caps),
+            Node::Any => pos < chars.len() && k(pos + 1, caps),
+            Node::Class(class) => match chars.get(pos) {
+                Some(&ch) => class.matches(ch) && k(pos + 1, caps),
+                None => false,
+            },
+            Node::Start => pos == 0 && k(pos, caps),
+            Node::End => pos == chars.len() && k(pos, caps),
+            Node::Group(inner, None) => self.match_node(inner, pos, caps, k),
+            Node::Group(inner, Some(index)) => {
+                let index = *index;
+                self.match_node(inner, pos, caps, &mut |end, caps| {
+                    let saved = caps[index];
+                    caps[index] = Some((pos, end));
+                    if k(end, caps) {
+                        true
+                    } else {
+                        caps[index] = saved;
+                        false
+                    }
+                })
+            }
+            Node::Concat(nodes) => self.match_concat(nodes, pos, caps, k),
+            Node::Alternate(alternatives) => alternatives
+                .iter()
+                .any(|alternative| self.match_node(alternative, pos, caps, k)),
+            Node::Repeat(repeat) => self.match_repeat(repeat, 0, pos, caps, k),
+        }
+    }
+
+    fn match_concat(
+        &self,
+        nodes: &[Node],
+        pos: usize,
+        caps: &mut Captures,
+        k: &mut dyn FnMut(usize, &mut Captures) -> bool,
+    ) -> bool {
+        match nodes.split_first() {
+            None => k(pos, caps),
+            Some((first, rest)) => self.match_node(first, pos, caps, &mut |next, caps| {
+                self.match_concat(rest, next, caps, k)
+            }),
+        }
+    }
+
+    fn match_repeat(
+        &self,
+        repeat: &Repeat,
+        count: usize,
+        pos: usize,
+        caps: &mut Captures,
+        k: &mut dyn FnMut(usize, &mut Captures) -> bool,
+    ) -> bool {
+        let satisfied = count >= repeat.min;
+        if satisfied && !repeat.greedy && k(pos, caps) {
+            return true;
+        }
+        if repeat.max.map_or(true, |max| count < max) {
+            let more = self.match_node(&repeat.node, pos, caps, &mut |next, caps| {
+                // An empty iteration can't lead anywhere new once the minimum
+                // is met, and would otherwise loop forever.
+                if next == pos && satisfied {
+                    return false;
+                }
+                self.match_repeat(repeat, count + 1, next, caps, k)
+            });
+            if more {
+                return true;
+            }
+        }
+        satisfied && repeat.greedy && k(pos, caps)
+    }
+}
+
+impl Class {
+    fn matches(&self, ch: char) -> bool {
+        let contains = self.items.iter().any(|item| match *item {
+            ClassItem::Range(lo, hi) => lo <= ch && ch <= hi,
+            ClassItem::Perl(perl, negated) => perl.matches(ch) != negated,
+        });
+        contains != self.negated
+    }
+}
+
+impl Perl {
+    fn matches(self, ch: char) -> bool {
+        match self {
+            Perl::Digit => ch.is_ascii_digit(),
+            Perl::Word => ch.is_alphanumeric() || ch == '_',
+            Perl::Space => ch.is_whitespace(),
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    groups: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn eat(&mut self, ch: char) -> bool {
+        if self.peek() == Some(ch) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_alternate(&mut self) -> Result<Node, String> {
+        let mut alternatives = vec![self.parse_concat()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_concat()?);
+        }
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Node::Alternate(alternatives)
+        })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, String> {
+        let mut nodes = Vec::new();
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_quantifier(atom)?);
+        }
+        Ok(Node::Concat(nodes))
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, String> {
+        let ch = self.chars[self.pos];
+        self.pos += 1;
+        Ok(match ch {
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '(' => {
+                let index = if self.eat('?') {
+                    if !self.eat(':') {
+                        return Err("unsupported group syntax, expected `(?:`".to_owned());
+                    }
+                    None
+                } else {
+                    self.groups += 1;
+                    Some(self.groups - 1)
+                };
+                let inner = self.parse_alternate()?;
+                if !self.eat(')') {
+                    return Err("unclosed `(`".to_owned());
+                }
+                Node::Group(Box::new(inner), index)
+            }
+            '[' => Node::Class(self.parse_class()?),
+            '\\' => match self.parse_escape()? {
+                ClassItem::Range(ch, _) => Node::Char(ch),
+                perl @ ClassItem::Perl(..) => Node::Class(Class {
+                    negated: false,
+                    items: vec![perl],
+                }),
+            },
+            '*' | '+' | '?' | '{' => {
+                return Err(format!("quantifier `{}` has nothing to repeat", ch));
+            }
+            _ => Node::Char(ch),
+        })
+    }
+
+    fn parse_quantifier(&mut self, atom: Node) -> Result<Node, String> {
+        let (min, max) = if self.eat('*') {
+            (0, None)
+        } else if self.eat('+') {
+            (1, None)
+        } else if self.eat('?') {
+            (0, Some(1))
+        } else if self.eat('{') {
+            let min = self.parse_number()?;
+            let max = if self.eat(',') {
+                if self.peek() == Some('}') {
+                    None
+                } else {
+                    Some(self.parse_number()?)
+                }
+            } else {
+                Some(min)
+            };
+            if !self.eat('}') {
+                return Err("unclosed `{`".to_owned());
+            }
+            if max.map_or(false, |max| max < min) {
+                return Err("invalid repetition range".to_owned());
+            }
+            (min, max)
+        } else {
+            return Ok(atom);
+        };
+        let greedy = !self.eat('?');
+        Ok(Node::Repeat(Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+            greedy,
+        }))
+    }
+
+    fn parse_number(&mut self) -> Result<usize, String> {
+        let begin = self.pos;
+        while self.peek().map_or(false, |ch| ch.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[begin..self.pos].iter().collect();
+        digits
+            .parse()
+            .map_err(|_| "expected number in repetition".to_owned())
+    }
+
+    fn parse_class(&mut self) -> Result<Class, String> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        let mut first = true;
+        loop {
+            let ch = match self.peek() {
+                Some(ch) => ch,
+                None => return Err("unclosed `[`".to_owned()),
+            };
+            self.pos += 1;
+            if ch == ']' && !first {
+                break;
+            }
+            first = false;
+            let item = if ch == '\\' {
+                self.parse_escape()?
+            } else {
+                ClassItem::Range(ch, ch)
+            };
+            let lo = match item {
+                ClassItem::Range(lo, _) => lo,
+                perl @ ClassItem::Perl(..) => {
+                    items.push(perl);
+                    continue;
+                }
+            };
+            if self.peek() == Some('-')
+                && self.chars.get(self.pos + 1).map_or(false, |&ch| ch != ']')
+            {
+                self.pos += 1;
+                let ch = self.chars[self.pos];
+                self.pos += 1;
+                let hi = if ch == '\\' {
+                    match self.parse_escape()? {
+                        ClassItem::Range(hi, _) => hi,
+                        ClassItem::Perl(..) => return Err("invalid class range".to_owned()),
+                    }
+                } else {
+                    ch
+                };
+                if hi < lo {
+                    return Err("invalid class range".to_owned());
+                }
+                items.push(ClassItem::Range(lo, hi));
+            } else {
+                items.push(item);
+            }
+        }
+        Ok(Class { negated, items })
+    }
+
+    // After a backslash: a Perl class like `\d`, or an escaped character as
+    // a single-character range.
+    fn parse_escape(&mut self) -> Result<ClassItem, String> {
+        let ch = match self.peek() {
+            Some(ch) => ch,
+            None => return Err("pattern ends with `\\`".to_owned()),
+        };
+        self.pos += 1;
+        let literal = match ch {
+            'd' => return Ok(ClassItem::Perl(Perl::Digit, false)),
+            'D' => return Ok(ClassItem::Perl(Perl::Digit, true)),
+            'w' => return Ok(ClassItem::Perl(Perl::Word, false)),
+            'W' => return Ok(ClassItem::Perl(Perl::Word, true)),
+            's' => return Ok(ClassItem::Perl(Perl::Space, false)),
+            'S' => return Ok(ClassItem::Perl(Perl::Space, true)),
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            _ if ch.is_alphanumeric() => {
+                return Err(format!("unsupported escape `\\{}`", ch));
+            }
+            _ => ch,
+        };
+        Ok(ClassItem::Range(literal, literal))
+    }
+}
@@ -1,12 +1,44 @@
+use crate::arith;
+use crate::ascii;
+use crate::case;
+use crate::config::{Config, TemplatePart};
 use crate::error::{Error, Result};
-use proc_macro::{token_stream, Delimiter, Ident, Span, TokenTree};
+use crate::inflect;
+use crate::number;
+use crate::regex::Regex;
+use proc_macro::{token_stream, Delimiter, Group, Ident, Span, TokenTree};
 use std::iter::Peekable;
+use std::mem;
+use std::panic;
 
 pub(crate) enum Segment {
     String(LitStr),
     Apostrophe(Span),
+    // Precedes the segment of an input identifier that was written raw, as in
+    // `r#type`.
+    Raw,
     Env(LitStr),
-    Modifier(Colon, Ident),
+    Modifier(Modifier),
+    Expr(Group),
+    // `_?`, an underscore that is pasted only between non-empty segments.
+    Separator,
+    // Separates the items of a list for `:join`, as in `$($part),*`.
+    Comma(Span),
+    // A `-` or `.` between segments, as in `my-crate` or `1 . 2`.
+    Punct(char),
+    // The components of a `$p:path` fragment, as in `std::io::Error`.
+    Path(Vec<String>),
+    // A parenthesized `($a $b)` or nested `[<$a $b>]` sub-sequence, whose
+    // concatenation a following modifier applies to as a whole.
+    Group(Vec<Segment>),
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum Context {
+    // Pasting an identifier, lifetime or literal from `[<...>]`.
+    Ident,
+    // Concatenating the string value of a name-value attribute like `#[doc]`.
+    Doc,
 }
 
 pub(crate) struct LitStr {
@@ -18,6 +50,22 @@ pub(crate) struct Colon {
     pub span: Span,
 }
 
+pub(crate) struct Modifier {
+    pub colon: Colon,
+    pub ident: Ident,
+    // Arguments in parentheses after the modifier name, as in
+    // `:strip_prefix("SDL_")`.
+    pub args: Vec<LitStr>,
+    // The closing parenthesis if there are arguments, otherwise the name.
+    pub end: Span,
+}
+
+impl Modifier {
+    fn error(&self, msg: &str) -> Error {
+        Error::new2(self.colon.span, self.end, msg)
+    }
+}
+
 pub(crate) fn parse(tokens: &mut Peekable<token_stream::IntoIter>) -> Result<Vec<Segment>> {
     let mut segments = Vec::new();
     while match tokens.peek() {
@@ -30,6 +78,7 @@ pub(crate) fn parse(tokens: &mut Peekable<token_stream::IntoIter>) -> Result<Vec
                 let mut fragment = ident.to_string();
                 if fragment.starts_with("r#") {
                     fragment = fragment.split_off(2);
+                    segments.push(Segment::Raw);
                 }
                 if fragment == "env"
                     && match tokens.peek() {
@@ -88,6 +137,8 @@ pub(crate) fn parse(tokens: &mut Peekable<token_stream::IntoIter>) -> Result<Vec
                             "unexpected token in env! macro",
                         ));
                     }
+                } else if fragment == "_" && eat_question(tokens) {
+                    segments.push(Segment::Separator);
                 } else {
                     segments.push(Segment::String(LitStr {
                         value: fragment,
@@ -102,11 +153,14 @@ pub(crate) fn parse(tokens: &mut Peekable<token_stream::IntoIter>) -> Result<Vec
                 }));
             }
             TokenTree::Punct(punct) => match punct.as_char() {
+                '_' if eat_question(tokens) => segments.push(Segment::Separator),
                 '_' => segments.push(Segment::String(LitStr {
                     value: "_".to_owned(),
                     span: punct.span(),
                 })),
                 '\'' => segments.push(Segment::Apostrophe(punct.span())),
+                ',' => segments.push(Segment::Comma(punct.span())),
+                '-' | '.' => segments.push(Segment::Punct(punct.as_char())),
                 ':' => {
                     let colon_span = punct.span();
                     let colon = Colon { span: colon_span };
@@ -117,18 +171,46 @@ pub(crate) fn parse(tokens: &mut Peekable<token_stream::IntoIter>) -> Result<Vec
                             return Err(Error::new(span, "expected identifier after `:`"));
                         }
                     };
-                    segments.push(Segment::Modifier(colon, ident));
+                    let mut modifier = Modifier {
+                        colon,
+                        end: ident.span(),
+                        ident,
+                        args: Vec::new(),
+                    };
+                    if let Some(TokenTree::Group(group)) = tokens.peek() {
+                        if group.delimiter() == Delimiter::Parenthesis {
+                            modifier.args = parse_args(group)?;
+                            modifier.end = group.span();
+                            tokens.next();
+                        }
+                    }
+                    segments.push(Segment::Modifier(modifier));
                 }
                 _ => return Err(Error::new(punct.span(), "unexpected punct")),
             },
             TokenTree::Group(group) => {
                 if group.delimiter() == Delimiter::None {
+                    if let Some(path) = parse_path(&group)? {
+                        segments.extend(path);
+                        continue;
+                    }
                     let mut inner = group.stream().into_iter().peekable();
                     let nested = parse(&mut inner)?;
                     if let Some(unexpected) = inner.next() {
                         return Err(Error::new(unexpected.span(), "unexpected token"));
                     }
                     segments.extend(nested);
+                } else if group.delimiter() == Delimiter::Brace {
+                    segments.push(Segment::Expr(group));
+                } else if group.delimiter() == Delimiter::Parenthesis {
+                    let mut inner = group.stream().into_iter().peekable();
+                    let nested = parse(&mut inner)?;
+                    if let Some(unexpected) = inner.next() {
+                        return Err(Error::new(unexpected.span(), "unexpected token"));
+                    }
+                    segments.push(Segment::Group(nested));
+                } else if group.delimiter() == Delimiter::Bracket {
+                    segments.push(Segment::Group(parse_nested_bracket(&group)?));
                 } else {
                     return Err(Error::new(group.span(), "unexpected token"));
                 }
@@ -138,20 +220,327 @@ pub(crate) fn parse(tokens: &mut Peekable<token_stream::IntoIter>) -> Result<Vec
     Ok(segments)
 }
 
-pub(crate) fn paste(segments: &[Segment]) -> Result<String> {
+// A `$p:path` fragment arrives as a None-delimited group of identifiers
+// separated by `::`, possibly followed by generic arguments. Other
+// None-delimited groups, including a path of one identifier, are parsed as
+// ordinary segments.
+fn parse_path(group: &Group) -> Result<Option<Vec<Segment>>> {
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    let is_punct = |token: &TokenTree, ch: char| match token {
+        TokenTree::Punct(punct) => punct.as_char() == ch,
+        _ => false,
+    };
+    let is_path_token = |token: &TokenTree| match token {
+        TokenTree::Ident(_) => true,
+        _ => is_punct(token, ':'),
+    };
+    // A single identifier, as from `$t:ty`, stays an ordinary segment.
+    let has_separator = tokens.iter().any(|token| is_punct(token, ':'));
+    let has_generics = tokens.iter().any(|token| is_punct(token, '<'));
+    let is_path = match tokens.first() {
+        Some(first) => {
+            is_path_token(first)
+                && has_separator
+                && (has_generics || tokens.iter().all(is_path_token))
+        }
+        None => false,
+    };
+    if !is_path {
+        return Ok(None);
+    }
+
+    let mut segments = Vec::new();
+    let mut components = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    let mut expect_ident = eat_path_separator(&mut tokens)?;
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ref ident) if expect_ident || components.is_empty() => {
+                let mut component = ident.to_string();
+                if component.starts_with("r#") {
+                    component = component.split_off(2);
+                    if segments.is_empty() {
+                        segments.push(Segment::Raw);
+                    }
+                }
+                components.push(component);
+                expect_ident = eat_path_separator(&mut tokens)?;
+                if !expect_ident {
+                    if let Some(unexpected) = tokens.next() {
+                        return Err(Error::new(unexpected.span(), "unexpected token in path"));
+                    }
+                }
+            }
+            TokenTree::Punct(ref punct) if punct.as_char() == '<' => {
+                return Err(generic_arguments(punct.span()));
+            }
+            unexpected => {
+                return Err(Error::new(unexpected.span(), "expected identifier in path"));
+            }
+        }
+    }
+    if expect_ident {
+        return Err(Error::new(group.span(), "expected identifier after `::`"));
+    }
+    segments.push(Segment::Path(components));
+    Ok(Some(segments))
+}
+
+// Consumes a `::` if there is one next. Generic arguments, which come after
+// the last component or after `::` in turbofish form, are an error.
+fn eat_path_separator(tokens: &mut Peekable<std::vec::IntoIter<TokenTree>>) -> Result<bool> {
+    match tokens.peek() {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+            return Err(generic_arguments(punct.span()));
+        }
+        _ => return Ok(false),
+    }
+    let first = tokens.next().unwrap();
+    match tokens.next() {
+        Some(TokenTree::Punct(ref punct)) if punct.as_char() == ':' => {}
+        _ => return Err(Error::new(first.span(), "expected `::` in path")),
+    }
+    if let Some(TokenTree::Punct(punct)) = tokens.peek() {
+        if punct.as_char() == '<' {
+            return Err(generic_arguments(punct.span()));
+        }
+    }
+    Ok(true)
+}
+
+fn generic_arguments(span: Span) -> Error {
+    Error::new(span, "generic arguments are not supported in a pasted path")
+}
+
+fn eat_question(tokens: &mut Peekable<token_stream::IntoIter>) -> bool {
+    match tokens.peek() {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '?' => {}
+        _ => return false,
+    }
+    tokens.next();
+    true
+}
+
+// `[< ... >]` within another.
+fn parse_nested_bracket(group: &Group) -> Result<Vec<Segment>> {
+    let mut inner = group.stream().into_iter().peekable();
+    match inner.next() {
+        Some(TokenTree::Punct(ref punct)) if punct.as_char() == '<' => {}
+        _ => return Err(Error::new(group.span(), "expected `[< ... >]`")),
+    }
+    let nested = parse(&mut inner)?;
+    match inner.next() {
+        Some(TokenTree::Punct(ref punct)) if punct.as_char() == '>' => {}
+        _ => return Err(Error::new(group.span(), "expected `[< ... >]`")),
+    }
+    if let Some(unexpected) = inner.next() {
+        return Err(Error::new(unexpected.span(), "unexpected token"));
+    }
+    Ok(nested)
+}
+
+const ARG_EXPECTED: &str = "expected string or integer literal, or identifier";
+
+fn parse_args(group: &Group) -> Result<Vec<LitStr>> {
+    let mut args = Vec::new();
+    let mut tokens = group.stream().into_iter();
+    while let Some(mut token) = tokens.next() {
+        if let TokenTree::Group(ref group) = token.clone() {
+            // A single token from a macro_rules fragment like `$default:ident`.
+            let mut inner = group.stream().into_iter();
+            if let (Delimiter::None, Some(single), None) =
+                (group.delimiter(), inner.next(), inner.next())
+            {
+                token = single;
+            }
+        }
+        let value = match &token {
+            TokenTree::Punct(punct) if punct.as_char() == '_' => "_".to_owned(),
+            TokenTree::Ident(ident) => {
+                let ident = ident.to_string();
+                if ident.starts_with("r#") {
+                    ident[2..].to_owned()
+                } else {
+                    ident
+                }
+            }
+            TokenTree::Literal(lit) => {
+                let repr = lit.to_string();
+                if repr.starts_with(|ch: char| ch.is_ascii_digit()) {
+                    repr
+                } else {
+                    match unescape_string(&repr) {
+                        Some(value) => value,
+                        None => return Err(Error::new(lit.span(), ARG_EXPECTED)),
+                    }
+                }
+            }
+            wrong => return Err(Error::new(wrong.span(), ARG_EXPECTED)),
+        };
+        args.push(LitStr {
+            value,
+            span: token.span(),
+        });
+        match tokens.next() {
+            Some(TokenTree::Punct(ref punct)) if punct.as_char() == ',' => {}
+            Some(wrong) => return Err(Error::new(wrong.span(), "expected `,`")),
+            None => break,
+        }
+    }
+    Ok(args)
+}
+
+// The value of a string literal given its source representation, or None if
+// it is not a string literal.
+pub(crate) fn unescape_string(repr: &str) -> Option<String> {
+    if repr.starts_with('r') {
+        // r#"..."#
+        let hashes = repr[1..].find('"')?;
+        return Some(repr[2 + hashes..repr.len() - 1 - hashes].to_owned());
+    }
+    if !repr.starts_with('"') || !repr.ends_with('"') || repr.len() < 2 {
+        return None;
+    }
+
+    let mut value = String::new();
+    let mut chars = repr[1..repr.len() - 1].chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            value.push(ch);
+            continue;
+        }
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            '\\' => value.push('\\'),
+            '\'' => value.push('\''),
+            '"' => value.push('"'),
+            'x' => {
+                let hi = chars.next()?.to_digit(8)?;
+                let lo = chars.next()?.to_digit(16)?;
+                value.push(std::char::from_u32(hi * 16 + lo)?);
+            }
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let mut code = 0;
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        '_' => {}
+                        digit => code = code * 16 + digit.to_digit(16)?,
+                    }
+                }
+                value.push(std::char::from_u32(code)?);
+            }
+            '\n' => {
+                while chars.peek().map_or(false, |ch| ch.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(value)
+}
+
+enum Piece {
+    Text(String),
+    // Digits from `:hex`, `:oct` or `:bin`, which `:pad` accepts even though
+    // they need not form a decimal integer.
+    Digits(String),
+    Path(Vec<String>),
+    Separator,
+    Comma(Span),
+}
+
+#[derive(Default)]
+struct Flags {
+    is_lifetime: bool,
+    is_raw: bool,
+    has_raw_input: bool,
+}
+
+pub(crate) fn paste(segments: &[Segment], context: Context, config: &Config) -> Result<String> {
+    let mut flags = Flags::default();
+    let mut pasted = evaluate(segments, context, config, &mut flags)?;
+    if context == Context::Ident
+        && (flags.is_raw || (flags.has_raw_input || config.raw_keywords) && is_keyword(&pasted))
+        && !is_raw_forbidden(&pasted)
+    {
+        pasted.insert_str(0, "r#");
+    }
+    if flags.is_lifetime {
+        pasted.insert(0, '\'');
+    }
+    if context == Context::Ident && is_len(segments) {
+        pasted.push_str("usize");
+    }
+    Ok(pasted)
+}
+
+// Whether the paste is a single segment measured by `:len`, as in
+// `[<$name:len>]`, whose value is then a `usize` literal. Anything after the
+// `:len` either changes the value or, like `[<$name:len u16>]`, gives it a
+// different type.
+fn is_len(segments: &[Segment]) -> bool {
+    let mut modifiers = 0;
+    for segment in segments.iter().rev() {
+        match segment {
+            Segment::Modifier(modifier) if modifiers == 0 => {
+                if modifier.ident.to_string() != "len" {
+                    return false;
+                }
+                modifiers += 1;
+            }
+            Segment::Modifier(_) => modifiers += 1,
+            _ => break,
+        }
+    }
+    modifiers > 0 && segments.len() == modifiers + 1
+}
+
+fn evaluate(
+    segments: &[Segment],
+    context: Context,
+    config: &Config,
+    flags: &mut Flags,
+) -> Result<String> {
     let mut evaluated = Vec::new();
-    let mut is_lifetime = false;
+    // Whether the modifiers so far apply to a parenthesized or nested group.
+    let mut after_group = false;
 
     for segment in segments {
         match segment {
             Segment::String(segment) => {
-                evaluated.push(segment.value.clone());
+                evaluated.push(Piece::Text(segment.value.clone()));
+            }
+            Segment::Separator => evaluated.push(Piece::Separator),
+            Segment::Comma(span) => evaluated.push(Piece::Comma(*span)),
+            Segment::Path(components) => evaluated.push(Piece::Path(components.clone())),
+            Segment::Punct(ch) => {
+                let text = match context {
+                    Context::Ident => "_".to_owned(),
+                    Context::Doc => ch.to_string(),
+                };
+                evaluated.push(Piece::Text(text));
             }
             Segment::Apostrophe(span) => {
-                if is_lifetime {
+                if flags.is_lifetime {
                     return Err(Error::new(*span, "unexpected lifetime"));
                 }
-                is_lifetime = true;
+                flags.is_lifetime = true;
+            }
+            Segment::Raw => flags.has_raw_input = true,
+            Segment::Expr(group) => {
+                evaluated.push(Piece::Text(arith::evaluate(group)?.to_string()));
+            }
+            Segment::Group(segments) => {
+                evaluated.push(Piece::Text(evaluate(segments, context, config, flags)?));
             }
             Segment::Env(var) => {
                 let resolved = match std::env::var(&var.value) {
@@ -164,70 +553,493 @@ pub(crate) fn paste(segments: &[Segment]) -> Result<String> {
                     }
                 };
                 let resolved = resolved.replace('-', "_");
-                evaluated.push(resolved);
+                evaluated.push(Piece::Text(resolved));
             }
-            Segment::Modifier(colon, ident) => {
+            Segment::Modifier(modifier) if modifier.ident.to_string() == "join" => {
+                check_arity("join", &modifier.args, modifier)?;
+                let separator = modifier.args.first().map_or("", |arg| &arg.value);
+                let mut items = Vec::new();
+                let mut item = Vec::new();
+                for piece in evaluated.drain(..) {
+                    match piece {
+                        Piece::Comma(_) => {
+                            items.push(concat(mem::replace(&mut item, Vec::new()), context)?);
+                        }
+                        piece => item.push(piece),
+                    }
+                }
+                items.push(concat(item, context)?);
+                items.retain(|item| !item.is_empty());
+                evaluated.push(Piece::Text(items.join(separator)));
+            }
+            Segment::Modifier(modifier) => {
+                let name = modifier.ident.to_string();
+                // An optional fragment that is empty leaves nothing for `:or`
+                // to apply to, unless it is grouped.
+                if name == "or" && !after_group {
+                    return Err(modifier
+                        .error("`:or` must follow a group, as in `($($prefix)?):or(default)`"));
+                }
                 let last = match evaluated.pop() {
-                    Some(last) => last,
-                    None => {
-                        return Err(Error::new2(colon.span, ident.span(), "unexpected modifier"))
+                    Some(Piece::Text(last)) => last,
+                    Some(Piece::Path(components)) => {
+                        if name == "first_segment" || name == "last_segment" {
+                            check_arity(&name, &modifier.args, modifier)?;
+                            let count = count_arg(modifier.args.first(), 1)?;
+                            let skip = if name == "first_segment" {
+                                0
+                            } else {
+                                components.len().saturating_sub(count)
+                            };
+                            let picked = &components[skip..components.len().min(skip + count)];
+                            evaluated.push(Piece::Text(join_path(picked, context)));
+                            continue;
+                        }
+                        join_path(&components, context)
                     }
-                };
-                match ident.to_string().as_str() {
-                    "lower" => {
-                        evaluated.push(last.to_lowercase());
-                    }
-                    "upper" => {
-                        evaluated.push(last.to_uppercase());
-                    }
-                    "snake" => {
-                        let mut acc = String::new();
-                        let mut prev = '_';
-                        for ch in last.chars() {
-                            if ch.is_uppercase() && prev != '_' {
-                                acc.push('_');
-                            }
-                            acc.push(ch);
-                            prev = ch;
+                    Some(Piece::Digits(digits)) => match name.as_str() {
+                        "pad" => {
+                            check_arity(&name, &modifier.args, modifier)?;
+                            evaluated.push(Piece::Text(pad(&digits, &modifier.args[0])?));
+                            continue;
                         }
-                        evaluated.push(acc.to_lowercase());
-                    }
-                    "camel" => {
-                        let mut acc = String::new();
-                        let mut prev = '_';
-                        for ch in last.chars() {
-                            if ch != '_' {
-                                if prev == '_' {
-                                    for chu in ch.to_uppercase() {
-                                        acc.push(chu);
-                                    }
-                                } else if prev.is_uppercase() {
-                                    for chl in ch.to_lowercase() {
-                                        acc.push(chl);
-                                    }
-                                } else {
-                                    acc.push(ch);
-                                }
-                            }
-                            prev = ch;
+                        // Changing the case of hex digits leaves them digits
+                        // that `:pad` still accepts.
+                        "upper" | "lower" => {
+                            let applied = apply_modifier(
+                                &digits,
+                                &name,
+                                &modifier.args,
+                                modifier,
+                                context,
+                                config,
+                            )?;
+                            evaluated.push(Piece::Digits(applied));
+                            continue;
                         }
-                        evaluated.push(acc);
-                    }
-                    _ => {
-                        return Err(Error::new2(
-                            colon.span,
-                            ident.span(),
-                            "unsupported modifier",
-                        ));
+                        _ => digits,
+                    },
+                    Some(Piece::Separator) | Some(Piece::Comma(_)) | None => {
+                        return Err(modifier.error("unexpected modifier"));
                     }
+                };
+                if name == "raw" {
+                    flags.is_raw = true;
                 }
+                let applied =
+                    apply_modifier(&last, &name, &modifier.args, modifier, context, config)?;
+                evaluated.push(match name.as_str() {
+                    "hex" | "oct" | "bin" => Piece::Digits(applied),
+                    _ => Piece::Text(applied),
+                });
             }
         }
+        after_group = match segment {
+            Segment::Group(_) => true,
+            Segment::Modifier(_) => after_group,
+            _ => false,
+        };
     }
 
-    let mut pasted = evaluated.into_iter().collect::<String>();
-    if is_lifetime {
-        pasted.insert(0, '\'');
+    concat(evaluated, context)
+}
+
+// Concatenates text, with an underscore for each run of optional separators
+// that falls between non-empty text.
+fn concat(pieces: Vec<Piece>, context: Context) -> Result<String> {
+    let mut pasted = String::new();
+    let mut separate = false;
+    for piece in pieces {
+        let text = match piece {
+            Piece::Text(text) | Piece::Digits(text) => text,
+            Piece::Path(components) => join_path(&components, context),
+            Piece::Separator => {
+                separate = true;
+                continue;
+            }
+            Piece::Comma(span) => {
+                return Err(Error::new(
+                    span,
+                    "unexpected `,` outside of a list for `:join`",
+                ));
+            }
+        };
+        if text.is_empty() {
+            continue;
+        }
+        if separate && !pasted.is_empty() {
+            pasted.push('_');
+        }
+        separate = false;
+        pasted.push_str(&text);
     }
     Ok(pasted)
 }
+
+// Path components are separated by an underscore in an identifier and written
+// out as a path in a doc string.
+fn join_path(components: &[String], context: Context) -> String {
+    match context {
+        Context::Ident => components.join("_"),
+        Context::Doc => components.join("::"),
+    }
+}
+
+// Names of the built-in modifiers, which user-defined modifiers from paste.toml
+// may refer to but not replace.
+pub(crate) const MODIFIERS: &[&str] = &[
+    "lower",
+    "upper",
+    "snake",
+    "camel",
+    "pascal",
+    "lower_camel",
+    "kebab",
+    "train",
+    "title",
+    "sentence",
+    "plural",
+    "singular",
+    "words",
+    "ordinal",
+    "alpha",
+    "strip_prefix",
+    "strip_suffix",
+    "replace",
+    "re",
+    "first",
+    "last",
+    "first_segment",
+    "last_segment",
+    "take",
+    "skip",
+    "slice",
+    "len",
+    "hex",
+    "oct",
+    "bin",
+    "pad",
+    "hash",
+    "truncate",
+    "ident_safe",
+    "ascii",
+    "raw",
+    "or",
+    "join",
+];
+
+// Minimum and maximum number of arguments taken by a built-in modifier.
+pub(crate) fn arity(name: &str) -> (usize, usize) {
+    match name {
+        "first" | "last" | "first_segment" | "last_segment" | "join" => (0, 1),
+        "strip_prefix" | "strip_suffix" | "take" | "skip" | "pad" | "truncate" | "or" => (1, 1),
+        "replace" | "re" | "slice" => (2, 2),
+        _ => (0, 0),
+    }
+}
+
+fn check_arity(name: &str, args: &[LitStr], modifier: &Modifier) -> Result<()> {
+    let (min, max) = arity(name);
+    if args.len() < min || args.len() > max {
+        return Err(modifier.error(&match (min, max) {
+            (0, 0) => "modifier takes no arguments".to_owned(),
+            (1, 1) => "expected 1 argument".to_owned(),
+            (0, 1) => "expected at most 1 argument".to_owned(),
+            (_, n) => format!("expected {} arguments", n),
+        }));
+    }
+    Ok(())
+}
+
+fn apply_modifier(
+    value: &str,
+    name: &str,
+    args: &[LitStr],
+    modifier: &Modifier,
+    context: Context,
+    config: &Config,
+) -> Result<String> {
+    if !MODIFIERS.contains(&name) && !config.aliases.templates.contains_key(name) {
+        return Err(modifier.error("unsupported modifier"));
+    }
+    check_arity(name, args, modifier)?;
+
+    Ok(match name {
+        "lower" => value.to_lowercase(),
+        "upper" => value.to_uppercase(),
+        "snake" => case::to_snake(value, config),
+        "camel" | "pascal" => case::to_camel(value, config),
+        "lower_camel" => case::to_lower_camel(value, config),
+        "kebab" | "train" | "title" | "sentence" if context == Context::Ident => {
+            return Err(modifier
+                .error("modifier does not produce an identifier, use it within #[doc = ...]"));
+        }
+        "kebab" => case::to_kebab(value, config),
+        "train" => case::to_train(value, config),
+        "title" => case::to_title(value, config),
+        "sentence" => case::to_sentence(value, config),
+        "plural" => inflect::to_plural(value),
+        "singular" => inflect::to_singular(value),
+        "words" => number::to_words(int_value(value, modifier)?),
+        "ordinal" => number::to_ordinal(int_value(value, modifier)?),
+        "alpha" => number::to_alpha(int_value(value, modifier)?),
+        "strip_prefix" => {
+            let prefix = &args[0];
+            if !value.starts_with(&prefix.value) {
+                return Err(Error::new(
+                    prefix.span,
+                    &format!("`{}` does not start with {:?}", value, prefix.value),
+                ));
+            }
+            value[prefix.value.len()..].to_owned()
+        }
+        "strip_suffix" => {
+            let suffix = &args[0];
+            if !value.ends_with(&suffix.value) {
+                return Err(Error::new(
+                    suffix.span,
+                    &format!("`{}` does not end with {:?}", value, suffix.value),
+                ));
+            }
+            value[..value.len() - suffix.value.len()].to_owned()
+        }
+        "replace" => {
+            let (from, to) = (&args[0], &args[1]);
+            if from.value.is_empty() {
+                return Err(Error::new(from.span, "cannot replace an empty string"));
+            }
+            value.replace(&from.value, &to.value)
+        }
+        "len" => value.chars().count().to_string(),
+        "hex" => format!("{:x}", int_value(value, modifier)?),
+        "oct" => format!("{:o}", int_value(value, modifier)?),
+        "bin" => format!("{:b}", int_value(value, modifier)?),
+        // The digits of `:hex`, `:oct` and `:bin` are padded in `evaluate`.
+        "pad" => pad(&int_value(value, modifier)?.to_string(), &args[0])?,
+        "first" | "take" => {
+            let n = count_arg(args.first(), 1)?;
+            value.chars().take(n).collect()
+        }
+        "last" => {
+            let n = count_arg(args.first(), 1)?;
+            let len = value.chars().count();
+            value.chars().skip(len.saturating_sub(n)).collect()
+        }
+        // Anything but a path is a single component, the path components are
+        // picked in `evaluate`.
+        "first_segment" | "last_segment" => {
+            if count_arg(args.first(), 1)? == 0 {
+                String::new()
+            } else {
+                value.to_owned()
+            }
+        }
+        "skip" => {
+            let n = count_arg(args.first(), 0)?;
+            value.chars().skip(n).collect()
+        }
+        "slice" => {
+            let begin = count_arg(args.first(), 0)?;
+            let end = count_arg(args.get(1), 0)?;
+            let len = value.chars().count();
+            if begin > end || end > len {
+                return Err(Error::new2(
+                    args[0].span,
+                    args[1].span,
+                    &format!(
+                        "range {}..{} is out of bounds for `{}` of {} chars",
+                        begin, end, value, len,
+                    ),
+                ));
+            }
+            value.chars().skip(begin).take(end - begin).collect()
+        }
+        // These act on more than the last segment, in `evaluate` above.
+        "raw" | "join" => value.to_owned(),
+        "or" if value.is_empty() => args[0].value.clone(),
+        "or" => value.to_owned(),
+        "ident_safe" => ident_safe(value),
+        "ascii" => match ascii::transliterate(value) {
+            Ok(transliterated) => transliterated,
+            Err(ch) => {
+                return Err(modifier.error(&format!(
+                    "cannot transliterate {:?} (U+{:04X}) to ASCII",
+                    ch, ch as u32,
+                )));
+            }
+        },
+        "hash" => format!("{}_{:08x}", value, fnv1a(value)),
+        "truncate" => {
+            let max = count_arg(args.first(), 0)?;
+            if max < HASH_LEN + 2 {
+                return Err(Error::new(
+                    args[0].span,
+                    &format!("expected length of at least {}", HASH_LEN + 2),
+                ));
+            }
+            if value.chars().count() <= max {
+                value.to_owned()
+            } else {
+                let prefix: String = value.chars().take(max - HASH_LEN - 1).collect();
+                format!("{}_{:08x}", prefix, fnv1a(value))
+            }
+        }
+        "re" => {
+            let (pattern, replacement) = (&args[0], &args[1]);
+            let regex = match Regex::new(&pattern.value) {
+                Ok(regex) => regex,
+                Err(msg) => {
+                    return Err(Error::new(
+                        pattern.span,
+                        &format!("invalid pattern: {}", msg),
+                    ));
+                }
+            };
+            let captures = match regex.find(value) {
+                Ok(Some(captures)) => captures,
+                Ok(None) => {
+                    return Err(Error::new(
+                        pattern.span,
+                        &format!("`{}` does not match pattern", value),
+                    ));
+                }
+                Err(msg) => return Err(Error::new(pattern.span, &msg)),
+            };
+            match regex.replace(value, &captures, &replacement.value) {
+                Ok(replaced) => replaced,
+                Err(msg) => return Err(Error::new(replacement.span, &msg)),
+            }
+        }
+        _ => {
+            let mut expanded = String::new();
+            let template_config = config.for_template();
+            for part in &config.aliases.templates[name] {
+                match part {
+                    TemplatePart::Text(text) => expanded.push_str(text),
+                    TemplatePart::Chain(chain) => {
+                        let mut chained = value.to_owned();
+                        for name in chain {
+                            chained = apply_modifier(
+                                &chained,
+                                name,
+                                &[],
+                                modifier,
+                                context,
+                                &template_config,
+                            )?;
+                        }
+                        expanded.push_str(&chained);
+                    }
+                }
+            }
+            expanded
+        }
+    })
+}
+
+// Strict and reserved keywords of every edition. Making an identifier raw is
+// harmless in editions where it is not a keyword, so there is no need to know
+// which edition the caller is compiled with.
+fn is_keyword(ident: &str) -> bool {
+    match ident {
+        "abstract" | "as" | "async" | "await" | "become" | "box" | "break" | "const"
+        | "continue" | "do" | "dyn" | "else" | "enum" | "extern" | "false" | "final" | "fn"
+        | "for" | "gen" | "if" | "impl" | "in" | "let" | "loop" | "macro" | "match" | "mod"
+        | "move" | "mut" | "override" | "priv" | "pub" | "ref" | "return" | "static" | "struct"
+        | "trait" | "true" | "try" | "type" | "typeof" | "unsafe" | "unsized" | "use"
+        | "virtual" | "where" | "while" | "yield" => true,
+        _ => false,
+    }
+}
+
+// Keywords that cannot be used as raw identifiers.
+fn is_raw_forbidden(ident: &str) -> bool {
+    match ident {
+        "" | "_" | "self" | "Self" | "super" | "crate" => true,
+        _ => ident.starts_with(|ch: char| ch.is_ascii_digit()),
+    }
+}
+
+// Maps every char that can't appear in an identifier to an underscore,
+// collapses runs of underscores, and prefixes an underscore if the result
+// would begin with a digit.
+fn ident_safe(value: &str) -> String {
+    let mut safe = String::new();
+    for ch in value.chars() {
+        let ch = if is_xid_continue(ch) { ch } else { '_' };
+        if ch != '_' || !safe.ends_with('_') {
+            safe.push(ch);
+        }
+    }
+    if safe.starts_with(|ch: char| ch != '_' && !is_xid_start(ch)) {
+        safe.insert(0, '_');
+    }
+    safe
+}
+
+// The Unicode identifier classes, which for non-ASCII chars are looked up by
+// asking the compiler whether they make an identifier.
+fn is_xid_start(ch: char) -> bool {
+    if ch.is_ascii() {
+        return ch.is_ascii_alphabetic();
+    }
+    is_ident(&ch.to_string())
+}
+
+fn is_xid_continue(ch: char) -> bool {
+    if ch.is_ascii() {
+        return ch.is_ascii_alphanumeric() || ch == '_';
+    }
+    is_ident(&format!("a{}", ch))
+}
+
+fn is_ident(name: &str) -> bool {
+    panic::catch_unwind(|| Ident::new(name, Span::call_site())).is_ok()
+}
+
+// Number of hex digits in the hash appended by `:hash` and `:truncate`.
+const HASH_LEN: usize = 8;
+
+// 32-bit FNV-1a of the UTF-8 bytes, which unlike std's hashers is specified
+// and so gives the same names on every compiler version and platform.
+fn fnv1a(value: &str) -> u32 {
+    let mut hash = 0x811c_9dc5_u32;
+    for &byte in value.as_bytes() {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+// A count of chars given as an integer argument, or the default if the
+// argument is omitted.
+fn count_arg(arg: Option<&LitStr>, default: usize) -> Result<usize> {
+    let arg = match arg {
+        Some(arg) => arg,
+        None => return Ok(default),
+    };
+    match arg.value.parse() {
+        Ok(count) => Ok(count),
+        Err(_) => Err(Error::new(arg.span, "expected integer")),
+    }
+}
+
+// Pads with zeros to the width in `arg`, which the formatting machinery only
+// supports up to u16::MAX.
+fn pad(digits: &str, arg: &LitStr) -> Result<String> {
+    let width = count_arg(Some(arg), 0)?;
+    if width > usize::from(std::u16::MAX) {
+        return Err(Error::new(
+            arg.span,
+            &format!("width must be at most {}", std::u16::MAX),
+        ));
+    }
+    Ok(format!("{:0>width$}", digits, width = width))
+}
+
+fn int_value(value: &str, modifier: &Modifier) -> Result<u64> {
+    match number::parse_int(value) {
+        Some(int) => Ok(int),
+        None => Err(modifier.error(&format!("expected integer, found `{}`", value))),
+    }
+}
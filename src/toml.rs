@@ -0,0 +1,165 @@
+// Just enough TOML for paste.toml: comments, `[table]` headers, and
+// `key = value` lines whose value is a string or a single-line array of
+// strings.
+
+pub(crate) enum Value {
+    String(String),
+    Array(Vec<String>),
+}
+
+pub(crate) struct Entry {
+    pub line: usize,
+    pub table: Option<String>,
+    pub key: String,
+    pub value: Value,
+}
+
+pub(crate) struct Error {
+    pub line: usize,
+    pub msg: String,
+}
+
+pub(crate) fn parse(source: &str) -> Result<Vec<Entry>, Error> {
+    let mut entries = Vec::new();
+    let mut table = None;
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let error = |msg: &str| Error {
+            line: line_number,
+            msg: msg.to_owned(),
+        };
+        let mut rest = line.trim_start();
+        if rest.is_empty() || rest.starts_with('#') {
+            continue;
+        }
+
+        if rest.starts_with('[') {
+            let close = match rest.find(']') {
+                Some(close) => close,
+                None => return Err(error("expected `]`")),
+            };
+            let name = rest[1..close].trim();
+            if !is_bare_key(name) {
+                return Err(error("expected table name"));
+            }
+            if !is_end_of_line(&rest[close + 1..]) {
+                return Err(error("unexpected characters after table header"));
+            }
+            table = Some(name.to_owned());
+            continue;
+        }
+
+        let key = match parse_key(&mut rest) {
+            Some(key) => key,
+            None => return Err(error("expected key")),
+        };
+        rest = rest.trim_start();
+        if !rest.starts_with('=') {
+            return Err(error("expected `=` after key"));
+        }
+        rest = rest[1..].trim_start();
+
+        let value = if rest.starts_with('[') {
+            rest = rest[1..].trim_start();
+            let mut array = Vec::new();
+            loop {
+                if rest.starts_with(']') {
+                    rest = &rest[1..];
+                    break;
+                }
+                match parse_string(&mut rest) {
+                    Some(string) => array.push(string),
+                    None => return Err(error("expected string in array")),
+                }
+                rest = rest.trim_start();
+                if rest.starts_with(',') {
+                    rest = rest[1..].trim_start();
+                } else if !rest.starts_with(']') {
+                    return Err(error("expected `,` or `]` in array"));
+                }
+            }
+            Value::Array(array)
+        } else {
+            match parse_string(&mut rest) {
+                Some(string) => Value::String(string),
+                None => return Err(error("expected string or array value")),
+            }
+        };
+        if !is_end_of_line(rest) {
+            return Err(error("unexpected characters after value"));
+        }
+
+        entries.push(Entry {
+            line: line_number,
+            table: table.clone(),
+            key,
+            value,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+}
+
+fn is_end_of_line(rest: &str) -> bool {
+    let rest = rest.trim_start();
+    rest.is_empty() || rest.starts_with('#')
+}
+
+fn parse_key(rest: &mut &str) -> Option<String> {
+    if rest.starts_with('"') || rest.starts_with('\'') {
+        return parse_string(rest);
+    }
+    let end = rest
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '-'))
+        .unwrap_or(rest.len());
+    if end == 0 {
+        return None;
+    }
+    let key = rest[..end].to_owned();
+    *rest = &rest[end..];
+    Some(key)
+}
+
+// Basic strings with the common escapes, or literal strings in single quotes.
+fn parse_string(rest: &mut &str) -> Option<String> {
+    let mut chars = rest.char_indices();
+    let quote = match chars.next() {
+        Some((_, quote)) if quote == '"' || quote == '\'' => quote,
+        _ => return None,
+    };
+    let mut string = String::new();
+    while let Some((i, ch)) = chars.next() {
+        if ch == quote {
+            *rest = &rest[i + 1..];
+            return Some(string);
+        }
+        if ch != '\\' || quote == '\'' {
+            string.push(ch);
+            continue;
+        }
+        match chars.next()?.1 {
+            '"' => string.push('"'),
+            '\\' => string.push('\\'),
+            'n' => string.push('\n'),
+            't' => string.push('\t'),
+            'r' => string.push('\r'),
+            'u' => {
+                let mut code = 0;
+                for _ in 0..4 {
+                    code = code * 16 + chars.next()?.1.to_digit(16)?;
+                }
+                string.push(std::char::from_u32(code)?);
+            }
+            _ => return None,
+        }
+    }
+    None
+}
@@ -0,0 +1,70 @@
+// Titlecase mappings from UnicodeData.txt and SpecialCasing.txt, limited to
+// the characters whose titlecase differs from their uppercase. Everything
+// else titlecases the same as `char::to_uppercase`.
+pub(crate) fn push_titlecase(acc: &mut String, ch: char) {
+    let title = match ch {
+        // Latin digraphs: ǆ → ǅ, not Ǆ.
+        '\u{1C4}'..='\u{1C6}' => '\u{1C5}',
+        '\u{1C7}'..='\u{1C9}' => '\u{1C8}',
+        '\u{1CA}'..='\u{1CC}' => '\u{1CB}',
+        '\u{1F1}'..='\u{1F3}' => '\u{1F2}',
+        // Greek with ypogegrammeni titlecases to prosgegrammeni.
+        '\u{1F80}'..='\u{1F87}' | '\u{1F90}'..='\u{1F97}' | '\u{1FA0}'..='\u{1FA7}' => {
+            char_offset(ch, 8)
+        }
+        '\u{1F88}'..='\u{1F8F}' | '\u{1F98}'..='\u{1F9F}' | '\u{1FA8}'..='\u{1FAF}' => ch,
+        '\u{1FB3}' | '\u{1FBC}' => '\u{1FBC}',
+        '\u{1FC3}' | '\u{1FCC}' => '\u{1FCC}',
+        '\u{1FF3}' | '\u{1FFC}' => '\u{1FFC}',
+        // Georgian Mkhedruli has no titlecase form distinct from itself.
+        '\u{10D0}'..='\u{10FA}' | '\u{10FD}'..='\u{10FF}' => ch,
+        _ => {
+            let special = match ch {
+                '\u{DF}' => "Ss",
+                '\u{FB00}' => "Ff",
+                '\u{FB01}' => "Fi",
+                '\u{FB02}' => "Fl",
+                '\u{FB03}' => "Ffi",
+                '\u{FB04}' => "Ffl",
+                '\u{FB05}' | '\u{FB06}' => "St",
+                '\u{587}' => "\u{535}\u{582}",
+                '\u{FB13}' => "\u{544}\u{576}",
+                '\u{FB14}' => "\u{544}\u{565}",
+                '\u{FB15}' => "\u{544}\u{56B}",
+                '\u{FB16}' => "\u{54E}\u{576}",
+                '\u{FB17}' => "\u{544}\u{56D}",
+                '\u{1FB2}' => "\u{1FBA}\u{345}",
+                '\u{1FB4}' => "\u{386}\u{345}",
+                '\u{1FB7}' => "\u{391}\u{342}\u{345}",
+                '\u{1FC2}' => "\u{1FCA}\u{345}",
+                '\u{1FC4}' => "\u{389}\u{345}",
+                '\u{1FC7}' => "\u{397}\u{342}\u{345}",
+                '\u{1FF2}' => "\u{1FFA}\u{345}",
+                '\u{1FF4}' => "\u{38F}\u{345}",
+                '\u{1FF7}' => "\u{3A9}\u{342}\u{345}",
+                _ => {
+                    acc.extend(ch.to_uppercase());
+                    return;
+                }
+            };
+            acc.push_str(special);
+            return;
+        }
+    };
+    acc.push(title);
+}
+
+// The characters of general category Lt. They are neither uppercase nor
+// lowercase according to std, but begin a word the same way as uppercase.
+pub(crate) fn is_titlecase(ch: char) -> bool {
+    match ch {
+        '\u{1C5}' | '\u{1C8}' | '\u{1CB}' | '\u{1F2}' => true,
+        '\u{1F88}'..='\u{1F8F}' | '\u{1F98}'..='\u{1F9F}' | '\u{1FA8}'..='\u{1FAF}' => true,
+        '\u{1FBC}' | '\u{1FCC}' | '\u{1FFC}' => true,
+        _ => false,
+    }
+}
+
+fn char_offset(ch: char, offset: u32) -> char {
+    std::char::from_u32(ch as u32 + offset).unwrap()
+}
@@ -0,0 +1,23 @@
+// Uses the modifiers defined in paste.toml next to this crate's Cargo.toml,
+// which apply to no other crate. Its acronyms and words apply within those
+// modifiers only.
+
+use paste::paste;
+
+macro_rules! m {
+    ($id:ident) => {
+        paste! {
+            pub struct [<$id:getter:camel>];
+            pub const [<$id:const_name>]: &str = stringify!([<$id:getter>]);
+            pub const SETTER: &str = stringify!([<$id:setter>]);
+            pub const SNAKE: &str = stringify!([<$id:snake>]);
+        }
+    };
+}
+
+m!(ColourName);
+
+paste! {
+    pub const TYPE_NAME: &str = stringify!([<url_parser:type_name>]);
+    pub const CAMEL: &str = stringify!([<url_parser:camel>]);
+}
@@ -55,6 +55,116 @@ fn test_case() {
     assert_eq!(doc, expected);
 }
 
+#[test]
+fn test_prose_case() {
+    let doc = paste! {
+        get_doc!(#[doc = "Returns the " user_id:sentence:lower " value."])
+    };
+
+    let expected = "Returns the user id value.";
+    assert_eq!(doc, expected);
+
+    let doc = paste! {
+        get_doc!(#[doc = UserId:title ", " user_id:sentence ", " UserId:kebab ", " user_id:train])
+    };
+
+    let expected = "User Id, User id, user-id, User-Id";
+    assert_eq!(doc, expected);
+}
+
+#[test]
+fn test_modifier_args() {
+    let doc = paste! {
+        get_doc!(#[doc = "Calls `" SDL_CreateWindow:strip_prefix("SDL_"):snake "` for " "$5":re("\\$(\\d)", "$$$1.00") "."])
+    };
+
+    let expected = "Calls `create_window` for $5.00.";
+    assert_eq!(doc, expected);
+}
+
+#[test]
+fn test_slicing_chars() {
+    let doc = paste! {
+        get_doc!(#[doc = "Größenänderung":take(5) " " "naïveté":last(4) " " "日本語":first])
+    };
+
+    let expected = "Größe veté 日";
+    assert_eq!(doc, expected);
+}
+
+#[test]
+fn test_escaped_chars() {
+    let doc = paste! {
+        get_doc!(#[doc = "\tabc":take(1) "caf\u{e9}":len " " "caf\u{e9}":last "\"" r"\d":first])
+    };
+
+    let expected = "\t4 \u{e9}\"\\";
+    assert_eq!(doc, expected);
+}
+
+#[test]
+fn test_arithmetic() {
+    macro_rules! get_field_doc {
+        ($n:literal) => {
+            paste! {
+                get_doc!(#[doc = "Field " {$n + 1} " of " {$n * 2 + 2} "."])
+            }
+        };
+    }
+
+    let expected = "Field 3 of 6.";
+    assert_eq!(get_field_doc!(2), expected);
+}
+
+#[test]
+fn test_grouping() {
+    let doc = paste! {
+        get_doc!(#[doc = "Returns the " (user "Id"):sentence:lower " of " [<Http Server>]:title "."])
+    };
+
+    let expected = "Returns the user id of Http Server.";
+    assert_eq!(doc, expected);
+}
+
+#[test]
+fn test_punct() {
+    macro_rules! get_crate_doc {
+        ($($name:ident)-+, $($version:tt),+) => {
+            paste! {
+                get_doc!(#[doc = "See " $($name)-+ " version " $($version).+ "."])
+            }
+        };
+    }
+
+    let expected = "See my-crate version 1.2.";
+    assert_eq!(get_crate_doc!(my - crate, 1, 2), expected);
+}
+
+#[test]
+fn test_path() {
+    macro_rules! get_type_doc {
+        ($p:path) => {
+            paste! {
+                get_doc!(#[doc = "Wraps [`" $p "`] as `" $p:last_segment "`."])
+            }
+        };
+    }
+
+    let expected = "Wraps [`std::io::Error`] as `Error`.";
+    assert_eq!(get_type_doc!(std::io::Error), expected);
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn test_unicode_case() {
+    let doc = paste! {
+        get_doc!(#[doc = "ǆemal_ǆungla":camel " " "日本語、テキスト":snake " " "ᾳδης":title])
+    };
+
+    let expected = "ǅemalǅungla 日本語_テキスト ᾼδης";
+    assert_eq!(doc, expected);
+}
+
 // https://github.com/dtolnay/paste/issues/63
 #[test]
 fn test_stringify() {
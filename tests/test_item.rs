@@ -163,6 +163,770 @@ mod test_to_camel {
     }
 }
 
+mod test_digits_option {
+    use paste::paste;
+
+    macro_rules! m {
+        ($name:ident => $id:ident $case:ident) => {
+            const $name: [&str; 3] = [
+                paste! { #![paste(digits = "left")] stringify!([<$id:$case>]) },
+                paste! { #![paste(digits = "right")] stringify!([<$id:$case>]) },
+                paste! { #![paste(digits = "separate")] stringify!([<$id:$case>]) },
+            ];
+        };
+    }
+
+    m!(VEC3D => Vec3D snake);
+    m!(VEC3D_CAMEL => Vec3D camel);
+    m!(SHA256_HASH => Sha256Hash snake);
+    m!(SHA256_HASH_CAMEL => Sha256Hash camel);
+    m!(UTF8_STRING => utf8_string snake);
+    m!(UTF8_STRING_CAMEL => utf8_string camel);
+    m!(MD5 => Md5 snake);
+    m!(MD5_CAMEL => Md5 camel);
+
+    #[test]
+    fn test_digits_option() {
+        assert_eq!(VEC3D, ["vec3_d", "vec_3d", "vec_3_d"]);
+        assert_eq!(VEC3D_CAMEL, ["Vec3D", "Vec3d", "Vec3D"]);
+        assert_eq!(SHA256_HASH, ["sha256_hash", "sha_256hash", "sha_256_hash"]);
+        assert_eq!(
+            SHA256_HASH_CAMEL,
+            ["Sha256Hash", "Sha256hash", "Sha256Hash"]
+        );
+        assert_eq!(UTF8_STRING, ["utf8_string", "utf_8_string", "utf_8_string"]);
+        assert_eq!(
+            UTF8_STRING_CAMEL,
+            ["Utf8String", "Utf8String", "Utf8String"]
+        );
+        assert_eq!(MD5, ["md5", "md_5", "md_5"]);
+        assert_eq!(MD5_CAMEL, ["Md5", "Md5", "Md5"]);
+    }
+}
+
+mod test_to_lower_camel {
+    use paste::paste;
+
+    macro_rules! m {
+        ($id:ident) => {
+            paste! {
+                const DEFAULT_LOWER_CAMEL: &str = stringify!([<$id:lower_camel>]);
+                const PASCAL: &str = stringify!([<$id:pascal>]);
+            }
+        };
+    }
+
+    m!(get_user_ID);
+
+    #[test]
+    fn test_to_lower_camel() {
+        assert_eq!(DEFAULT_LOWER_CAMEL, "getUserId");
+        assert_eq!(PASCAL, "GetUserId");
+    }
+}
+
+mod test_acronym_option {
+    use paste::paste;
+
+    paste! {
+        #![paste(acronyms = "HTTP, URL, ID, IPv6")]
+        const URL_PARSER: &str = stringify!([<url_parser:camel>]);
+        const USER_ID: &str = stringify!([<user_id:lower_camel>]);
+        const HTTP_URL_PARSER: &str = stringify!([<HTTPURLParser:snake>]);
+        const IPV6_ADDRESS: &str = stringify!([<IPv6Address:snake>]);
+        const IPV6_CAMEL: &str = stringify!([<ipv6_address:camel>]);
+        const IDE: &str = stringify!([<IDEConfig:snake>]);
+    }
+
+    #[test]
+    fn test_acronym_option() {
+        assert_eq!(URL_PARSER, "URLParser");
+        assert_eq!(USER_ID, "userID");
+        assert_eq!(HTTP_URL_PARSER, "http_url_parser");
+        assert_eq!(IPV6_ADDRESS, "ipv6_address");
+        assert_eq!(IPV6_CAMEL, "IPv6Address");
+        assert_eq!(IDE, "ide_config");
+    }
+}
+
+mod test_underscores {
+    use paste::paste;
+
+    macro_rules! m {
+        ($name:ident => $id:ident) => {
+            paste! {
+                const $name: [&str; 2] = [stringify!([<$id:snake>]), stringify!([<$id:camel>])];
+            }
+        };
+    }
+
+    m!(PRIVATE => _Private);
+    m!(RESERVED => __reserved__);
+    m!(FOO_BAR => Foo_Bar);
+    m!(UNUSED_VALUE => unused__Value_);
+
+    #[test]
+    fn test_underscores() {
+        assert_eq!(PRIVATE, ["_private", "_Private"]);
+        assert_eq!(RESERVED, ["__reserved__", "__Reserved__"]);
+        assert_eq!(FOO_BAR, ["foo_bar", "FooBar"]);
+        assert_eq!(UNUSED_VALUE, ["unused_value_", "UnusedValue_"]);
+    }
+}
+
+mod test_acronyms {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($id:ident => $snake:ident $camel:ident),*) => {
+            paste! {
+                $(
+                    const [<$snake:upper>]: &str = stringify!([<$id:snake>]);
+                    const [<$camel:upper>]: &str = stringify!([<$id:camel>]);
+                )*
+            }
+        };
+    }
+
+    m! {
+        HTTPServer => http_server http_server_camel,
+        IOError => io_error io_error_camel,
+        parseURL => parse_url parse_url_camel,
+        HTTP2Server => http2_server http2_server_camel
+    }
+
+    #[test]
+    fn test_acronyms() {
+        assert_eq!(HTTP_SERVER, "http_server");
+        assert_eq!(HTTP_SERVER_CAMEL, "HttpServer");
+        assert_eq!(IO_ERROR, "io_error");
+        assert_eq!(IO_ERROR_CAMEL, "IoError");
+        assert_eq!(PARSE_URL, "parse_url");
+        assert_eq!(PARSE_URL_CAMEL, "ParseUrl");
+        assert_eq!(HTTP2_SERVER, "http2_server");
+        assert_eq!(HTTP2_SERVER_CAMEL, "Http2Server");
+    }
+}
+
+mod test_inflection {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($entity:ident => $plural:ident $singular:ident),*) => {
+            paste! {
+                $(
+                    const [<$plural:upper>]: &str = stringify!([<$entity:snake:plural>]);
+                    const [<$singular:upper>]: &str = stringify!([<$entity:plural:singular>]);
+                )*
+            }
+        };
+    }
+
+    m! {
+        User => users user,
+        Category => categories category,
+        Address => addresses address,
+        OrderStatus => order_statuses order_status,
+        Person => people person,
+        Key => keys key,
+        Box => boxes box_,
+        Analysis => analyses analysis,
+        Knife => knives knife,
+        Sheep => sheep_plural sheep_singular,
+        Cause => causes cause,
+        HTTPCache => http_caches http_cache
+    }
+
+    paste! {
+        const UPPER: &str = stringify!([<USER_CATEGORY:plural>]);
+        const CAMEL: &str = stringify!([<AddressBook:plural>]);
+    }
+
+    #[test]
+    fn test_inflection() {
+        assert_eq!(USERS, "users");
+        assert_eq!(USER, "User");
+        assert_eq!(CATEGORIES, "categories");
+        assert_eq!(CATEGORY, "Category");
+        assert_eq!(ADDRESSES, "addresses");
+        assert_eq!(ADDRESS, "Address");
+        assert_eq!(ORDER_STATUSES, "order_statuses");
+        assert_eq!(ORDER_STATUS, "OrderStatus");
+        assert_eq!(PEOPLE, "people");
+        assert_eq!(PERSON, "Person");
+        assert_eq!(KEYS, "keys");
+        assert_eq!(KEY, "Key");
+        assert_eq!(BOXES, "boxes");
+        assert_eq!(BOX_, "Box");
+        assert_eq!(ANALYSES, "analyses");
+        assert_eq!(ANALYSIS, "Analysis");
+        assert_eq!(KNIVES, "knives");
+        assert_eq!(KNIFE, "Knife");
+        assert_eq!(SHEEP_PLURAL, "sheep");
+        assert_eq!(SHEEP_SINGULAR, "Sheep");
+        assert_eq!(CAUSES, "causes");
+        assert_eq!(CAUSE, "Cause");
+        assert_eq!(HTTP_CACHES, "http_caches");
+        assert_eq!(HTTP_CACHE, "HTTPCache");
+        assert_eq!(UPPER, "USER_CATEGORIES");
+        assert_eq!(CAMEL, "AddressBooks");
+    }
+}
+
+mod test_number_names {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($index:tt)*) => {
+            paste! {
+                $(
+                    const [<$index:words:upper>]: [&str; 3] = [
+                        stringify!([<$index:ordinal>]),
+                        stringify!([<$index:alpha>]),
+                        stringify!([<$index:alpha:lower>]),
+                    ];
+                )*
+            }
+        };
+    }
+
+    m!(0 1 2 11 21 0x1F 100 1_005 702);
+
+    #[test]
+    fn test_number_names() {
+        assert_eq!(ZERO, ["zeroth", "A", "a"]);
+        assert_eq!(ONE, ["first", "B", "b"]);
+        assert_eq!(TWO, ["second", "C", "c"]);
+        assert_eq!(ELEVEN, ["eleventh", "L", "l"]);
+        assert_eq!(TWENTY_ONE, ["twenty_first", "V", "v"]);
+        assert_eq!(THIRTY_ONE, ["thirty_first", "AF", "af"]);
+        assert_eq!(ONE_HUNDRED, ["one_hundredth", "CW", "cw"]);
+        assert_eq!(ONE_THOUSAND_FIVE, ["one_thousand_fifth", "ALR", "alr"]);
+        assert_eq!(SEVEN_HUNDRED_TWO, ["seven_hundred_second", "AAA", "aaa"]);
+    }
+}
+
+mod test_string_modifiers {
+    use paste::paste;
+
+    macro_rules! m {
+        ($prefix:literal, $($name:ident)*) => {
+            paste! {
+                $(
+                    const [<$name:strip_prefix($prefix):snake:upper>]: &str =
+                        stringify!([<$name:strip_prefix($prefix):strip_suffix("Request")>]);
+                )*
+            }
+        };
+    }
+
+    m!("SDL_", SDL_CreateWindowRequest SDL_DestroyWindowRequest);
+
+    paste! {
+        const REPLACE: &str = stringify!([<ColourPicker:replace("Colour", "Color"):snake>]);
+        const ESCAPE: &str = stringify!([<a_b_c:replace("_", r"\u{5f}x"):replace("\\u{5f}", "")>]);
+    }
+
+    #[test]
+    fn test_string_modifiers() {
+        assert_eq!(CREATE_WINDOW_REQUEST, "CreateWindow");
+        assert_eq!(DESTROY_WINDOW_REQUEST, "DestroyWindow");
+        assert_eq!(REPLACE, "color_picker");
+        assert_eq!(ESCAPE, "axbxc");
+    }
+}
+
+mod test_regex_modifier {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($name:ident)*) => {
+            paste! {
+                $(
+                    const [<$name:re("^(.*?)(Handler|Listener)$", "on_$1"):snake:upper>]: &str =
+                        stringify!([<$name:re("^(\\w*?)(?:Handler|Listener)$", "${1}_callback")>]);
+                )*
+            }
+        };
+    }
+
+    m!(ClickHandler KeyPressListener);
+
+    paste! {
+        const DIGITS: &str = stringify!([<vec3_f32:re("[0-9]+", "N")>]);
+        const LAZY: &str = stringify!([<a_b_c:re("_.*?", "")>]);
+        const GREEDY: &str = stringify!([<a_b_c:re("_.*", "")>]);
+        const CLASS: &str = stringify!([<FooBar:re("[A-Z][^A-Z]{2}$", "$0$0")>]);
+        const ALTERNATE: &str = stringify!([<get_value:re("^(?:get|set)_(v)a(l)?", "$2_$1")>]);
+        const OPTIONAL: &str = stringify!([<Item:re("^(Ite)(x)?m$", "${2}z$1")>]);
+    }
+
+    #[test]
+    fn test_regex_modifier() {
+        assert_eq!(ON_CLICK, "Click_callback");
+        assert_eq!(ON_KEY_PRESS, "KeyPress_callback");
+        assert_eq!(DIGITS, "vecN_f32");
+        assert_eq!(LAZY, "ab_c");
+        assert_eq!(GREEDY, "a");
+        assert_eq!(CLASS, "FooBarBar");
+        assert_eq!(ALTERNATE, "l_vue");
+        assert_eq!(OPTIONAL, "zIte");
+    }
+}
+
+mod test_slicing {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($ty:ident)*) => {
+            paste! {
+                $(
+                    struct [<$ty Pair>]<[<$ty:first>]>([<$ty:first>], [<$ty:first>]);
+                    const [<$ty:upper>]: [&str; 5] = [
+                        stringify!([<$ty:last>]),
+                        stringify!([<$ty:last(3)>]),
+                        stringify!([<$ty:take(3)>]),
+                        stringify!([<$ty:skip(4)>]),
+                        stringify!([<$ty:slice(1, 4)>]),
+                    ];
+                )*
+            }
+        };
+    }
+
+    m!(Token Value);
+
+    macro_rules! ty {
+        ($ty:ty) => {
+            paste! {
+                const TY: [&str; 2] = [stringify!([<$ty:first>]), stringify!([<$ty:last(3)>])];
+            }
+        };
+    }
+
+    ty!(Token);
+
+    #[test]
+    fn test_slicing() {
+        let _: TokenPair<u8> = TokenPair(0, 0);
+        let _: ValuePair<i8> = ValuePair(0, 0);
+        assert_eq!(TOKEN, ["n", "ken", "Tok", "n", "oke"]);
+        assert_eq!(VALUE, ["e", "lue", "Val", "e", "alu"]);
+        assert_eq!(TY, ["T", "ken"]);
+    }
+}
+
+mod test_len {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($name:ident)*) => {
+            paste! {
+                $(
+                    const [<$name:upper _BUF>]: [u8; [<$name:len>]] = [0; [<$name:len>]];
+                )*
+                const NAMES_LEN: u16 = 0 $(+ [<$name:len u16>])*;
+            }
+        };
+    }
+
+    m!(width height);
+
+    #[test]
+    fn test_len() {
+        assert_eq!(paste!(stringify!([<height:len>])), "6usize");
+        assert_eq!(WIDTH_BUF.len(), 5);
+        assert_eq!(HEIGHT_BUF.len(), 6);
+        assert_eq!(NAMES_LEN, 11);
+    }
+}
+
+mod test_radix {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($n:literal)*) => {
+            paste! {
+                $(
+                    const [<REG_ $n:hex:upper>]: [&str; 5] = [
+                        stringify!([<op_ $n:pad(3)>]),
+                        stringify!([<oct_ $n:oct>]),
+                        stringify!([<bin_ $n:bin:pad(8)>]),
+                        stringify!([<x $n:hex:pad(4)>]),
+                        stringify!([<REG_0x $n:hex:upper:pad(4)>]),
+                    ];
+                )*
+            }
+        };
+    }
+
+    m!(7 31 0x1_00 010u8);
+
+    #[test]
+    fn test_radix() {
+        assert_eq!(
+            REG_7,
+            ["op_007", "oct_7", "bin_00000111", "x0007", "REG_0x0007"]
+        );
+        assert_eq!(
+            REG_1F,
+            ["op_031", "oct_37", "bin_00011111", "x001f", "REG_0x001F"]
+        );
+        assert_eq!(
+            REG_100,
+            ["op_256", "oct_400", "bin_100000000", "x0100", "REG_0x0100"]
+        );
+        assert_eq!(
+            REG_A,
+            ["op_010", "oct_12", "bin_00001010", "x000a", "REG_0x000A"]
+        );
+    }
+}
+
+mod test_arithmetic {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($n:literal)*) => {
+            paste! {
+                $(
+                    const [<STATE_ $n>]: &str = stringify!([<next_state_ {$n + 1}>]);
+                    const [<OFFSET_ $n>]: usize = [<{$n * 8}usize>];
+                )*
+            }
+        };
+    }
+
+    m!(0 2);
+
+    paste! {
+        const PRECEDENCE: &str = stringify!([<x_ {1 + 2 * 3} _ {(1 + 2) * 3} _ {17 / 5 % 2 - 1}>]);
+        const WORDS: &str = stringify!([<{0x10 + 1}:ordinal>]);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(STATE_0, "next_state_1");
+        assert_eq!(STATE_2, "next_state_3");
+        assert_eq!(OFFSET_0, 0);
+        assert_eq!(OFFSET_2, 16);
+        assert_eq!(PRECEDENCE, "x_7_9_0");
+        assert_eq!(WORDS, "seventeenth");
+    }
+}
+
+mod test_hash {
+    use paste::paste;
+
+    paste! {
+        const HASH: &str = stringify!([<ffi:hash>]);
+        const SHORT: &str = stringify!([<my_type:truncate(16)>]);
+        const LONG: &str = stringify!([<my_crate_very_long_module_path_SomeType:truncate(17)>]);
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(HASH, "ffi_ac09484c");
+        assert_eq!(SHORT, "my_type");
+        assert_eq!(LONG, "my_crate_4da226b7");
+    }
+}
+
+mod test_ident_safe {
+    use paste::paste;
+
+    paste! {
+        const VERSION: &str = stringify!([<"v1.2 beta+build":ident_safe>]);
+        const DIGIT: &str = stringify!([<"3d.model":ident_safe:upper>]);
+        const ESCAPE: &str = stringify!([<r"C:\Program Files\":ident_safe>]);
+        const COLLAPSE: &str = stringify!([<get_ "--dry--run--":ident_safe>]);
+        const SUPERSCRIPT: &str = stringify!([<"größe²":ident_safe>]);
+        const COMBINING: &str = stringify!([<"\u{301}e\u{301}":ident_safe>]);
+    }
+
+    #[test]
+    fn test_ident_safe() {
+        assert_eq!(VERSION, "v1_2_beta_build");
+        assert_eq!(DIGIT, "_3D_MODEL");
+        assert_eq!(ESCAPE, "C_Program_Files_");
+        assert_eq!(COLLAPSE, "get__dry_run_");
+        assert_eq!(SUPERSCRIPT, "größe_");
+        // The compiler normalizes identifiers to NFC.
+        assert_eq!(COMBINING, "_\u{301}\u{e9}");
+    }
+}
+
+mod test_ascii {
+    use paste::paste;
+
+    paste! {
+        const SIZE: &str = stringify!([<"Größe":ascii:snake>]);
+        const CAFE: &str = stringify!([<"café_crème":ascii:upper>]);
+        const LIGATURE: &str = stringify!([<"Æsir_œuvre_ﬁle":ascii>]);
+        const POLISH: &str = stringify!([<"Łódź":ascii>]);
+    }
+
+    #[test]
+    fn test_ascii() {
+        assert_eq!(SIZE, "grosse");
+        assert_eq!(CAFE, "CAFE_CREME");
+        assert_eq!(LIGATURE, "AEsir_oeuvre_file");
+        assert_eq!(POLISH, "Lodz");
+    }
+}
+
+mod test_raw {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($field:ident)*) => {
+            paste! {
+                #[derive(Default)]
+                pub struct Fields {
+                    $(pub [<$field>]: u8,)*
+                }
+            }
+        };
+    }
+
+    m!(r#type r#match name);
+
+    paste! {
+        fn [<for:raw>]() -> u8 { 1 }
+        fn [<get_ value:raw>]() -> u8 { 2 }
+    }
+
+    mod automatic {
+        use paste::paste;
+
+        paste! {
+            #![paste(raw_keywords = true)]
+
+            pub fn [<ty pe>]() -> u8 { 3 }
+            pub fn [<g en>]() -> u8 { 4 }
+            pub fn [<self_ type>]() -> u8 { 5 }
+        }
+    }
+
+    #[test]
+    fn test_raw() {
+        let fields = Fields::default();
+        let _ = (fields.r#type, fields.r#match, fields.name);
+        assert_eq!(r#for(), 1);
+        assert_eq!(get_value(), 2);
+        assert_eq!(automatic::r#type(), 3);
+        assert_eq!(automatic::r#gen(), 4);
+        assert_eq!(automatic::self_type(), 5);
+    }
+}
+
+#[rustversion::since(1.83)]
+mod test_raw_lifetime {
+    // Raw lifetimes can't be written in the edition 2018 source of this test,
+    // but they can be pasted.
+
+    use paste::paste;
+
+    paste! {
+        pub struct Lifetimes<[<'a:raw>], [<'type:raw>]>(pub &[<'a:raw>] u8, pub &[<'type:raw>] u8);
+
+        const NAMES: [&str; 2] = [stringify!([<'a:raw>]), stringify!([<'ty pe:raw>])];
+    }
+
+    mod automatic {
+        use paste::paste;
+
+        paste! {
+            #![paste(raw_keywords = true)]
+
+            pub struct Ref<[<'ty pe>]>(pub &[<'ty pe>] u8);
+        }
+    }
+
+    #[test]
+    fn test_raw_lifetime() {
+        let byte = 1;
+        let lifetimes = Lifetimes(&byte, &byte);
+        let reference = automatic::Ref(&byte);
+        assert_eq!(*lifetimes.0 + *lifetimes.1 + *reference.0, 3);
+        assert_eq!(NAMES, ["'r#a", "'r#type"]);
+    }
+}
+
+mod test_grouping {
+    use paste::paste;
+
+    macro_rules! m {
+        ($a:ident $b:ident) => {
+            paste! {
+                const [<($a $b):snake:upper>]: [&str; 3] = [
+                    stringify!([<get_ ($a $b):snake>]),
+                    stringify!([<[<$a $b>]:snake _x>]),
+                    stringify!([<(get_ ($a:upper $b)):camel>]),
+                ];
+            }
+        };
+    }
+
+    m!(Http Server);
+
+    #[test]
+    fn test_grouping() {
+        assert_eq!(
+            HTTP_SERVER,
+            ["get_http_server", "http_server_x", "GetHttpServer"],
+        );
+    }
+}
+
+mod test_optional_separator {
+    use paste::paste;
+
+    macro_rules! m {
+        ($(@$prefix:ident)? $name:ident $(. $suffix:ident)?) => {
+            paste! {
+                const [<$($prefix:upper)? _? $name:upper _? $($suffix:upper)?>]: [&str; 2] = [
+                    stringify!([<$($prefix)? _? $name _? _? $($suffix)?>]),
+                    stringify!([<($($prefix)?):or(default) _ $name>]),
+                ];
+            }
+        };
+    }
+
+    m!(@user name);
+    m!(ID);
+    m!(len.max);
+    m!(@my total.bytes);
+
+    #[test]
+    fn test_optional_separator() {
+        assert_eq!(USER_NAME, ["user_name", "user_name"]);
+        assert_eq!(ID, ["ID", "default_ID"]);
+        assert_eq!(LEN_MAX, ["len_max", "default_len"]);
+        assert_eq!(MY_TOTAL_BYTES, ["my_total_bytes", "my_total"]);
+    }
+}
+
+mod test_join {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($part:ident),*) => {
+            paste! {
+                const [<$($part:upper),* :join(_)>]: [&str; 3] = [
+                    stringify!([<get_ $($part),* :join(_)>]),
+                    stringify!([<($($part),*:join):camel>]),
+                    stringify!([<[<$($part:first),*:join>]:upper>]),
+                ];
+            }
+        };
+    }
+
+    m!(a);
+    m!(b, c);
+    m!(user, account, id);
+
+    #[test]
+    fn test_join() {
+        assert_eq!(A, ["get_a", "A", "A"]);
+        assert_eq!(B_C, ["get_b_c", "Bc", "BC"]);
+        assert_eq!(
+            USER_ACCOUNT_ID,
+            ["get_user_account_id", "Useraccountid", "UAI"]
+        );
+    }
+}
+
+mod test_punct {
+    use paste::paste;
+
+    macro_rules! m {
+        ($($name:ident)-+, $($version:tt),+) => {
+            paste! {
+                const [<($($name)-+):upper _V $($version).+>]: &str = stringify!([<$($name)-+ . rs>]);
+            }
+        };
+    }
+
+    m!(my - crate, 1, 2);
+    m!(serde - json - core, 0, 10, 1);
+
+    #[test]
+    fn test_punct() {
+        assert_eq!(MY_CRATE_V1_2, "my_crate_rs");
+        assert_eq!(SERDE_JSON_CORE_V0_10_1, "serde_json_core_rs");
+    }
+}
+
+mod test_path {
+    use paste::paste;
+
+    macro_rules! m {
+        ($name:ident => $p:path) => {
+            paste! {
+                const $name: [&str; 6] = [
+                    stringify!([<$p>]),
+                    stringify!([<$p:snake>]),
+                    stringify!([<$p:last_segment>]),
+                    stringify!([<$p:first_segment _ $p:last_segment(2):camel>]),
+                    stringify!([<$p:last_segment:first>]),
+                    stringify!([<$p:last>]),
+                ];
+            }
+        };
+    }
+
+    m!(ERROR => std::io::Error);
+    m!(ROOTED => ::core::fmt::Result);
+    m!(SINGLE => String);
+
+    #[test]
+    fn test_path() {
+        assert_eq!(
+            ERROR,
+            [
+                "std_io_Error",
+                "std_io_error",
+                "Error",
+                "std_IoError",
+                "E",
+                "r"
+            ],
+        );
+        assert_eq!(
+            ROOTED,
+            [
+                "core_fmt_Result",
+                "core_fmt_result",
+                "Result",
+                "core_FmtResult",
+                "R",
+                "t"
+            ],
+        );
+        // A single identifier is a path of one segment.
+        assert_eq!(
+            SINGLE,
+            ["String", "string", "String", "String_String", "S", "g"]
+        );
+    }
+}
+
+mod test_config_file {
+    // The crate in tests/config has a paste.toml that defines the modifiers
+    // used there, with acronyms and words that apply to nothing else.
+
+    #[test]
+    fn test_config_file() {
+        let _ = paste_test_config::GetColorName;
+        assert_eq!(paste_test_config::COLOR_NAME, "get_color_name");
+        assert_eq!(paste_test_config::SETTER, "set_ColourName");
+        assert_eq!(paste_test_config::SNAKE, "colour_name");
+        assert_eq!(paste_test_config::TYPE_NAME, "URLParser");
+        assert_eq!(paste_test_config::CAMEL, "UrlParser");
+    }
+}
+
 mod test_doc_expr {
     // https://github.com/dtolnay/paste/issues/29
 
@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<a {} b>]() {}
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<state_ {n + 1}>]() {}
+}
+
+fn main() {}
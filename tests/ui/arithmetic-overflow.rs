@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<state_ {0 - 1}>]() {}
+}
+
+fn main() {}
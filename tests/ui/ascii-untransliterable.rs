@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<"名前":ascii>]() {}
+}
+
+fn main() {}
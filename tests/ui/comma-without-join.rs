@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<get_ a, b>]() {}
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use paste::paste;
+
+paste! {
+    #![paste(digits = "middle")]
+    fn [<Vec3D:snake>]() {}
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<CreateWindow:snake("x")>]() {}
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<get_ field:ordinal>]() {}
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use paste::paste;
+
+macro_rules! m {
+    ($(@$prefix:ident)? $name:ident) => {
+        paste! {
+            fn [<get_ $($prefix)? :or(default) _ $name>]() {}
+        }
+    };
+}
+
+m!(name);
+
+fn main() {}
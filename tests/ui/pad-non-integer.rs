@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<x_ bad:pad(5)>]() {}
+}
+
+fn main() {}
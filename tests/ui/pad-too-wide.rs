@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<x_ 1:pad(70000)>]() {}
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use paste::paste;
+
+macro_rules! m {
+    ($p:path) => {
+        paste! {
+            struct [<$p:last_segment Wrapper>];
+        }
+    };
+}
+
+m!(std::vec::Vec<u8>);
+
+fn main() {}
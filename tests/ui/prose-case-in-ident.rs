@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<user_id:kebab>]() {}
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<ClickListener:re("^(.*)Handler$", "on_$1")>]() {}
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<aaaaaaaaaaaaaaaaaaaaaaaaaaaaaac:re("^(a*)*b$", "x")>]() {}
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<Token:slice(2, 8)>]() {}
+}
+
+fn main() {}
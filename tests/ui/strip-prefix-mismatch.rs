@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<SDL_CreateWindow:strip_prefix("GL_")>]() {}
+}
+
+fn main() {}
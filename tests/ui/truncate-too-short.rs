@@ -0,0 +1,7 @@
+use paste::paste;
+
+paste! {
+    fn [<some_long_function_name:truncate(8)>]() {}
+}
+
+fn main() {}
@@ -1,7 +1,7 @@
 use paste::paste;
 
 paste! {
-    fn [<a {} b>]() {}
+    fn [<a [b] c>]() {}
 }
 
 fn main() {}
@@ -0,0 +1,8 @@
+use paste::paste;
+
+paste! {
+    #![paste(acronym = "HTTP")]
+    fn [<http_get:snake>]() {}
+}
+
+fn main() {}